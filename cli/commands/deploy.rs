@@ -15,7 +15,7 @@
 // along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::CurrentNetwork;
-use aleo_rust::{AleoAPIClient, Encryptor, ProgramManager, RecordFinder};
+use aleo_rust::{AleoAPIClient, CreditAmount, Encryptor, ProgramManager, RecordFinder};
 use snarkvm::prelude::{Ciphertext, Plaintext, PrivateKey, ProgramID, Record};
 
 use anyhow::{anyhow, ensure, Result};
@@ -36,7 +36,7 @@ pub struct Deploy {
     endpoint: Option<String>,
     /// Deployment fee in credits
     #[clap(short, long)]
-    fee: f64,
+    fee: CreditAmount,
     /// The record to spend the fee from
     #[clap(short, long)]
     record: Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
@@ -59,10 +59,10 @@ impl Deploy {
             "Private key or private key ciphertext required to deploy a program"
         );
 
-        ensure!(self.fee > 0.0, "Deployment fee must be greater than 0");
+        ensure!(self.fee.microcredits() > 0, "Deployment fee must be greater than 0");
 
         // Convert deployment fee to microcredits
-        let fee_microcredits = (self.fee * 1000000.0) as u64;
+        let fee_microcredits = self.fee.microcredits();
 
         // Get strings for the program for logging
         let program_string = self.program_id.to_string();
@@ -122,8 +122,9 @@ impl Deploy {
 
         // Deploy the program
         println!("Attempting to deploy program: {}", program_string.bright_blue());
-        let result =
-            program_manager.deploy_program(self.program_id, fee_microcredits, fee_record, self.password.as_deref());
+        let result = program_manager
+            .deploy_program(self.program_id, self.fee, fee_record, self.password.as_deref())
+            .map(|deployment| deployment.transaction_id.to_string());
 
         // Inform the user of the result of the program deployment
         if result.is_err() {
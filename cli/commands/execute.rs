@@ -15,7 +15,7 @@
 // along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::CurrentNetwork;
-use aleo_rust::{AleoAPIClient, Encryptor, ProgramManager, RecordFinder};
+use aleo_rust::{AleoAPIClient, CreditAmount, Encryptor, ProgramManager, RecordFinder};
 use snarkvm::prelude::{Ciphertext, Identifier, Plaintext, PrivateKey, ProgramID, Record, Value};
 
 use anyhow::{anyhow, ensure, Result};
@@ -39,7 +39,7 @@ pub struct Execute {
     endpoint: Option<String>,
     /// Execution fee in credits
     #[clap(long)]
-    fee: f64,
+    fee: CreditAmount,
     /// The record to spend the fee from
     #[clap(short, long)]
     record: Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
@@ -61,11 +61,11 @@ impl Execute {
             !(self.private_key.is_none() && self.ciphertext.is_none()),
             "Private key or private key ciphertext required to execute a function"
         );
-        ensure!(self.fee > 0.0, "Fee must be greater than 0 to execute a program");
+        ensure!(self.fee.microcredits() > 0, "Fee must be greater than 0 to execute a program");
 
         // Convert execution fee to microcredits
         let fee_credits = self.fee;
-        let fee_microcredits = (fee_credits * 1000000.0) as u64;
+        let fee_microcredits = fee_credits.microcredits();
 
         // Get strings for the program and function for logging
         let program_string = self.program_id.to_string();
@@ -123,7 +123,7 @@ impl Execute {
             self.program_id,
             self.function,
             self.inputs.iter(),
-            fee_microcredits,
+            fee_credits,
             fee_record,
             self.password.as_deref(),
         );
@@ -15,7 +15,7 @@
 // along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::CurrentNetwork;
-use aleo_rust::{AleoAPIClient, Encryptor, ProgramManager, RecordFinder};
+use aleo_rust::{AleoAPIClient, CreditAmount, Encryptor, ProgramManager, RecordFinder};
 use snarkvm::prelude::{Address, Ciphertext, Plaintext, PrivateKey, Record};
 
 use anyhow::{anyhow, ensure, Result};
@@ -42,7 +42,7 @@ pub struct Transfer {
     endpoint: Option<String>,
     /// Transaction fee in credits
     #[clap(short, long)]
-    fee: f64,
+    fee: CreditAmount,
     /// Private key used to generate the transfer
     #[clap(short='k', long, conflicts_with_all = &["ciphertext", "password"])]
     private_key: Option<PrivateKey<CurrentNetwork>>,
@@ -58,7 +58,7 @@ impl Transfer {
     pub fn parse(self) -> Result<String> {
         // Check for config errors
         ensure!(self.amount > 0f64, "Transfer amount must be greater than 0 credits");
-        ensure!(self.fee > 0f64, "fee must be greater than zero to make a transfer");
+        ensure!(self.fee.microcredits() > 0, "fee must be greater than zero to make a transfer");
 
         ensure!(
             !(self.private_key.is_none() && self.ciphertext.is_none()),
@@ -68,7 +68,7 @@ impl Transfer {
         // Convert transfer amount and fee to microcredits
         let amount_microcredits = (self.amount * 1000000.0) as u64;
         let fee_credits = self.fee;
-        let fee_microcredits = (fee_credits * 1000000.0) as u64;
+        let fee_microcredits = fee_credits.microcredits();
 
         println!(
             "{}",
@@ -130,7 +130,7 @@ impl Transfer {
         // Execute the transfer
         let transfer = program_manager.transfer(
             amount_microcredits,
-            fee_microcredits,
+            fee_credits,
             self.recipient,
             self.password.as_deref(),
             input_record,
@@ -149,7 +149,7 @@ use requests::*;
 mod routes;
 pub use routes::*;
 
-use aleo_rust::{AleoAPIClient, Encryptor, ProgramManager, RecordFinder};
+use aleo_rust::{AleoAPIClient, CreditAmount, Encryptor, ProgramManager, RecordFinder};
 use snarkvm::{
     console::{
         account::{Address, PrivateKey},
@@ -114,10 +114,17 @@ impl<N: Network> Rest<N> {
             request.fee_record.unwrap()
         };
 
-        // Deploy the program and return the resulting transaction id
-        let transaction_id =
-            spawn_blocking!(program_manager.deploy_program(request.program.id(), request.fee, fee_record, None))?;
+        // Deploy the program and return the resulting transaction id, JSON encoded like the
+        // broadcast response returned by the execute and transfer routes
+        let deployment = spawn_blocking!(program_manager.deploy_program(
+            request.program.id(),
+            CreditAmount::from_microcredits(request.fee),
+            fee_record,
+            None
+        ))?;
 
+        let transaction_id = serde_json::to_string(&deployment.transaction_id)
+            .map_err(|error| reject::custom(RestError::Request(error.to_string())))?;
         Ok(reply::json(&transaction_id))
     }
 
@@ -150,7 +157,7 @@ impl<N: Network> Rest<N> {
             request.program_id,
             request.program_function,
             request.inputs.iter(),
-            request.fee,
+            CreditAmount::from_microcredits(request.fee),
             fee_record,
             None,
         ))?;
@@ -192,7 +199,7 @@ impl<N: Network> Rest<N> {
         // Run the transfer program within credits.aleo and return the resulting transaction id
         let transaction_id = spawn_blocking!(program_manager.transfer(
             request.amount,
-            request.fee,
+            CreditAmount::from_microcredits(request.fee),
             request.recipient,
             None,
             amount_record,
@@ -0,0 +1,50 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use rand::rngs::OsRng;
+
+/// Generate a new random Aleo account, returning its private key, view key and address.
+///
+/// The private key is sampled from the operating system's cryptographically secure random
+/// number generator. The private key is the only secret needed to recover the account, so it
+/// should be backed up by the caller.
+pub fn generate_account<N: Network>() -> Result<(PrivateKey<N>, ViewKey<N>, Address<N>)> {
+    let private_key = PrivateKey::<N>::new(&mut OsRng)?;
+    let view_key = ViewKey::try_from(&private_key)?;
+    let address = Address::try_from(&view_key)?;
+    Ok((private_key, view_key, address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_account() {
+        let (private_key, view_key, address) = generate_account::<Testnet3>().unwrap();
+
+        // Ensure the keys and address belong to the same account
+        assert_eq!(view_key, ViewKey::try_from(&private_key).unwrap());
+        assert_eq!(address, Address::try_from(&private_key).unwrap());
+
+        // Ensure each account generated is different
+        let (other_private_key, _, other_address) = generate_account::<Testnet3>().unwrap();
+        assert_ne!(private_key, other_private_key);
+        assert_ne!(address, other_address);
+    }
+}
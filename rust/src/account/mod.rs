@@ -20,3 +20,6 @@ use super::*;
 
 pub mod encryptor;
 pub use encryptor::*;
+
+pub mod generate;
+pub use generate::*;
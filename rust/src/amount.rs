@@ -0,0 +1,136 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Amounts of Aleo credits which can't be confused with amounts of microcredits
+
+use super::*;
+
+use std::fmt;
+
+// Number of microcredits in one credit
+const MICROCREDITS_PER_CREDIT: u64 = 1_000_000;
+// Number of decimal places of a credit amount
+const DECIMAL_PLACES: usize = 6;
+
+/// An amount of Aleo credits, used to specify the fees paid for transactions.
+///
+/// The amount is stored exactly in microcredits. It can be parsed from a decimal amount of
+/// credits (i.e. `"1.5"` is 1,500,000 microcredits) or created from an amount of microcredits with
+/// [CreditAmount::from_microcredits], so the unit of the amount is always explicit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreditAmount(u64);
+
+impl CreditAmount {
+    /// Create an amount from a number of microcredits
+    pub const fn from_microcredits(microcredits: u64) -> Self {
+        Self(microcredits)
+    }
+
+    /// Create an amount from a whole number of credits
+    pub fn from_credits(credits: u64) -> Result<Self> {
+        credits
+            .checked_mul(MICROCREDITS_PER_CREDIT)
+            .map(Self)
+            .ok_or_else(|| anyhow!("{credits} credits is larger than the maximum amount of credits"))
+    }
+
+    /// Get the amount in microcredits
+    pub const fn microcredits(&self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for CreditAmount {
+    type Err = Error;
+
+    /// Parse a decimal amount of credits with at most 6 decimal places
+    fn from_str(amount: &str) -> Result<Self> {
+        let (credits, decimals) = match amount.trim().split_once('.') {
+            Some((credits, decimals)) => (credits, Some(decimals)),
+            None => (amount.trim(), None),
+        };
+        let is_number = |digits: &str| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit());
+        ensure!(
+            is_number(credits) && decimals.map_or(true, is_number),
+            "Invalid amount of credits {amount:?}, amounts must be positive decimal numbers such as 1.5"
+        );
+        let decimals = decimals.unwrap_or_default();
+        ensure!(
+            decimals.len() <= DECIMAL_PLACES,
+            "Invalid amount of credits {amount:?}, amounts can have at most {DECIMAL_PLACES} decimal places"
+        );
+        let credits = Self::from_credits(u64::from_str(credits)?)?.0;
+        let microcredits = match decimals.is_empty() {
+            true => 0,
+            false => u64::from_str(&format!("{decimals:0<DECIMAL_PLACES$}"))?,
+        };
+        credits
+            .checked_add(microcredits)
+            .map(Self)
+            .ok_or_else(|| anyhow!("{amount:?} is larger than the maximum amount of credits"))
+    }
+}
+
+impl fmt::Display for CreditAmount {
+    /// Display the amount in credits, without trailing zeros
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let credits = self.0 / MICROCREDITS_PER_CREDIT;
+        let microcredits = self.0 % MICROCREDITS_PER_CREDIT;
+        match microcredits {
+            0 => write!(f, "{credits}"),
+            _ => {
+                let decimals = format!("{microcredits:0>DECIMAL_PLACES$}");
+                write!(f, "{credits}.{}", decimals.trim_end_matches('0'))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credit_amount_parsing() {
+        // Ensure decimal amounts of credits are converted exactly to microcredits
+        assert_eq!(CreditAmount::from_str("1.5").unwrap().microcredits(), 1_500_000);
+        assert_eq!(CreditAmount::from_str("0.7").unwrap().microcredits(), 700_000);
+        assert_eq!(CreditAmount::from_str("0.000001").unwrap().microcredits(), 1);
+        assert_eq!(CreditAmount::from_str("42").unwrap().microcredits(), 42_000_000);
+        assert_eq!(CreditAmount::from_str(" 3.25 ").unwrap(), CreditAmount::from_microcredits(3_250_000));
+        assert_eq!(CreditAmount::from_credits(2).unwrap(), CreditAmount::from_str("2.0").unwrap());
+
+        // Ensure amounts with too many decimal places or which aren't positive numbers are rejected
+        let error = CreditAmount::from_str("0.0000001").unwrap_err();
+        assert!(error.to_string().contains("at most 6 decimal places"));
+        for amount in ["", ".5", "1.", "-1", "+1", "1.5.0", "1,5", "1e6", "credits"] {
+            assert!(CreditAmount::from_str(amount).is_err(), "{amount:?} should be rejected");
+        }
+
+        // Ensure amounts which overflow are rejected
+        assert!(CreditAmount::from_str("18446744073709.551616").is_err());
+        assert!(CreditAmount::from_str("18446744073710").is_err());
+        assert_eq!(CreditAmount::from_str("18446744073709.551615").unwrap().microcredits(), u64::MAX);
+    }
+
+    #[test]
+    fn test_credit_amount_display() {
+        for amount in ["1.5", "0.7", "0.000001", "42", "0"] {
+            assert_eq!(CreditAmount::from_str(amount).unwrap().to_string(), amount);
+        }
+        assert_eq!(CreditAmount::from_microcredits(1_230_000).to_string(), "1.23");
+    }
+}
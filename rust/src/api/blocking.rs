@@ -16,12 +16,14 @@
 
 use super::*;
 
+use rayon::{prelude::*, ThreadPoolBuilder};
+
 #[cfg(not(feature = "async"))]
 #[allow(clippy::type_complexity)]
-impl<N: Network> AleoAPIClient<N> {
+impl<N: Network, T: HttpTransport> AleoAPIClient<N, T> {
     pub fn latest_height(&self) -> Result<u32> {
         let url = format!("{}/{}/latest/height", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
+        match self.get(&url)?.json() {
             Ok(height) => Ok(height),
             Err(error) => bail!("Failed to parse the latest block height: {error}"),
         }
@@ -29,7 +31,7 @@ impl<N: Network> AleoAPIClient<N> {
 
     pub fn latest_hash(&self) -> Result<N::BlockHash> {
         let url = format!("{}/{}/latest/hash", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
+        match self.get(&url)?.json() {
             Ok(hash) => Ok(hash),
             Err(error) => bail!("Failed to parse the latest block hash: {error}"),
         }
@@ -37,7 +39,7 @@ impl<N: Network> AleoAPIClient<N> {
 
     pub fn latest_block(&self) -> Result<Block<N>> {
         let url = format!("{}/{}/latest/block", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
+        match self.get(&url)?.json() {
             Ok(block) => Ok(block),
             Err(error) => bail!("Failed to parse the latest block: {error}"),
         }
@@ -45,12 +47,20 @@ impl<N: Network> AleoAPIClient<N> {
 
     pub fn get_block(&self, height: u32) -> Result<Block<N>> {
         let url = format!("{}/{}/block/{height}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
+        match self.get(&url)?.json() {
             Ok(block) => Ok(block),
             Err(error) => bail!("Failed to parse block {height}: {error}"),
         }
     }
 
+    pub fn get_block_by_hash(&self, block_hash: N::BlockHash) -> Result<Block<N>> {
+        let url = format!("{}/{}/block/{block_hash}", self.base_url, self.network_id);
+        match self.get(&url)?.json() {
+            Ok(block) => Ok(block),
+            Err(error) => bail!("Failed to parse block {block_hash}: {error}"),
+        }
+    }
+
     pub fn get_blocks(&self, start_height: u32, end_height: u32) -> Result<Vec<Block<N>>> {
         if start_height >= end_height {
             bail!("Start height must be less than end height");
@@ -59,7 +69,7 @@ impl<N: Network> AleoAPIClient<N> {
         }
 
         let url = format!("{}/{}/blocks?start={start_height}&end={end_height}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
+        match self.get(&url)?.json() {
             Ok(blocks) => Ok(blocks),
             Err(error) => {
                 bail!("Failed to parse blocks {start_height} (inclusive) to {end_height} (exclusive): {error}")
@@ -69,7 +79,7 @@ impl<N: Network> AleoAPIClient<N> {
 
     pub fn get_transaction(&self, transaction_id: N::TransactionID) -> Result<Transaction<N>> {
         let url = format!("{}/{}/transaction/{transaction_id}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
+        match self.get(&url)?.json() {
             Ok(transaction) => Ok(transaction),
             Err(error) => bail!("Failed to parse transaction '{transaction_id}': {error}"),
         }
@@ -77,7 +87,7 @@ impl<N: Network> AleoAPIClient<N> {
 
     pub fn get_memory_pool_transactions(&self) -> Result<Vec<Transaction<N>>> {
         let url = format!("{}/{}/memoryPool/transactions", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
+        match self.get(&url)?.json() {
             Ok(transactions) => Ok(transactions),
             Err(error) => bail!("Failed to parse memory pool transactions: {error}"),
         }
@@ -88,29 +98,72 @@ impl<N: Network> AleoAPIClient<N> {
         let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
         // Perform the request.
         let url = format!("{}/{}/program/{program_id}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
+        match self.get(&url)?.json() {
             Ok(program) => Ok(program),
             Err(error) => bail!("Failed to parse program {program_id}: {error}"),
         }
     }
 
+    /// Get the program deployed by a deployment transaction
+    pub fn get_program_by_deployment(&self, transaction_id: N::TransactionID) -> Result<Program<N>> {
+        match self.get_transaction(transaction_id)? {
+            Transaction::Deploy(_, _, deployment, _) => Ok(deployment.program().clone()),
+            Transaction::Execute(..) => {
+                bail!("Transaction '{transaction_id}' is an execution and did not deploy a program")
+            }
+        }
+    }
+
     pub fn find_block_hash(&self, transaction_id: N::TransactionID) -> Result<N::BlockHash> {
         let url = format!("{}/{}/find/blockHash/{transaction_id}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
+        match self.get(&url)?.json() {
             Ok(hash) => Ok(hash),
             Err(error) => bail!("Failed to parse block hash: {error}"),
         }
     }
 
+    /// Get a Merkle proof that a transaction is included in the block containing it.
+    ///
+    /// Nodes don't serve inclusion proofs, so the block containing the transaction is fetched
+    /// and the proof is computed from its transactions. The proof can then be checked against a
+    /// block header obtained from a trusted source with [InclusionProof::verify].
+    pub fn get_transaction_inclusion_proof(&self, transaction_id: N::TransactionID) -> Result<InclusionProof<N>> {
+        let block_hash = self.find_block_hash(transaction_id)?;
+        let block = self.get_block_by_hash(block_hash)?;
+        ensure!(block.hash() == block_hash, "The node returned block {} instead of block {block_hash}", block.hash());
+        let path = block.transactions().to_path(transaction_id)?;
+        Ok(InclusionProof::new(transaction_id, block_hash, path))
+    }
+
     /// Returns the transition ID that contains the given `input ID` or `output ID`.
     pub fn find_transition_id(&self, input_or_output_id: Field<N>) -> Result<N::TransitionID> {
         let url = format!("{}/{}/find/transitionID/{input_or_output_id}", self.base_url, self.network_id);
-        match self.client.get(&url).call()?.into_json() {
+        match self.get(&url)?.json() {
             Ok(transition_id) => Ok(transition_id),
             Err(error) => bail!("Failed to parse transition ID: {error}"),
         }
     }
 
+    /// Determine if a record with the given serial number has been spent on chain.
+    ///
+    /// The serial number of a spent record is the ID of the transition input spending it, so the
+    /// record is spent if the node finds a transition for it. A serial number the node can't find
+    /// is unspent, while any other failure to query the node is returned as an error.
+    pub fn is_serial_number_spent(&self, serial_number: Field<N>) -> Result<bool> {
+        let url = format!("{}/{}/find/transitionID/{serial_number}", self.base_url, self.network_id);
+        let response = self.transport.request(HttpMethod::Get, &url, None, self.timeout)?;
+        if response.is_success() {
+            response
+                .json::<N::TransitionID>()
+                .map_err(|error| anyhow!("Failed to parse the transition ID spending '{serial_number}': {error}"))?;
+            Ok(true)
+        } else if response.status == 404 || response.body.contains("Failed to find the transition ID") {
+            Ok(false)
+        } else {
+            bail!("{url}: status code {}: {}", response.status, response.body)
+        }
+    }
+
     /// Scans the ledger for records that match the given view key.
     pub fn scan(
         &self,
@@ -159,6 +212,198 @@ impl<N: Network> AleoAPIClient<N> {
         Ok(records)
     }
 
+    /// Scans the ledger for records that match the given view key, skipping records whose
+    /// commitments have already been seen by previous scans using the same scan state.
+    ///
+    /// The scan state is updated with every commitment checked and the highest block height
+    /// scanned, so it can be persisted (it implements `Serialize` and `Deserialize`) and passed to
+    /// later scans in order to only process records which are new.
+    pub fn scan_with_state(
+        &self,
+        view_key: impl TryInto<ViewKey<N>>,
+        block_heights: Range<u32>,
+        scan_state: &mut ScanState<N>,
+    ) -> Result<Vec<(Field<N>, Record<N, Ciphertext<N>>)>> {
+        // Prepare the view key.
+        let view_key = view_key.try_into().map_err(|_| anyhow!("Invalid view key"))?;
+        // Compute the x-coordinate of the address.
+        let address_x_coordinate = view_key.to_address().to_x_coordinate();
+
+        ensure!(
+            block_heights.start < block_heights.end,
+            "The start block height must be less than the end block height"
+        );
+
+        // Initialize a vector for the records.
+        let mut records = Vec::new();
+
+        for start_height in block_heights.clone().step_by(50) {
+            let end_height = block_heights.end.min(start_height + 50);
+            println!("Searching blocks {} to {} for records...", start_height, end_height);
+
+            // Only check the ownership of records which haven't been seen before.
+            for (commitment, record) in
+                self.get_blocks(start_height, end_height)?.into_iter().flat_map(|block| block.into_records())
+            {
+                if scan_state.insert(commitment)
+                    && record.is_owner_with_address_x_coordinate(&view_key, &address_x_coordinate)
+                {
+                    records.push((commitment, record));
+                }
+            }
+            scan_state.update_height(end_height - 1);
+        }
+
+        Ok(records)
+    }
+
+    /// Scans the ledger for records owned by the given view key and decrypts them.
+    ///
+    /// Checking the ownership of a record and decrypting it doesn't depend on any other record, so
+    /// the records of each batch of blocks fetched are processed in parallel. The work is spread
+    /// over a thread pool with the given number of threads, or over rayon's global thread pool if
+    /// no number is given. Records are returned in the order they appear in the ledger.
+    pub fn scan_records(
+        &self,
+        view_key: impl TryInto<ViewKey<N>>,
+        block_heights: Range<u32>,
+        threads: Option<usize>,
+    ) -> Result<Vec<(Field<N>, Record<N, Plaintext<N>>)>> {
+        // Prepare the view key.
+        let view_key = view_key.try_into().map_err(|_| anyhow!("Invalid view key"))?;
+
+        ensure!(
+            block_heights.start < block_heights.end,
+            "The start block height must be less than the end block height"
+        );
+        ensure!(threads != Some(0), "The number of threads used to decrypt records must be greater than 0");
+        let thread_pool = threads.map(|threads| ThreadPoolBuilder::new().num_threads(threads).build()).transpose()?;
+
+        // Initialize a vector for the records.
+        let mut records = Vec::new();
+
+        for start_height in block_heights.clone().step_by(50) {
+            let end_height = block_heights.end.min(start_height + 50);
+            println!("Searching blocks {} to {} for records...", start_height, end_height);
+
+            let ciphertexts =
+                self.get_blocks(start_height, end_height)?.into_iter().flat_map(|block| block.into_records()).collect();
+            records.extend(match &thread_pool {
+                Some(thread_pool) => thread_pool.install(|| Self::decrypt_owned_records(&view_key, ciphertexts)),
+                None => Self::decrypt_owned_records(&view_key, ciphertexts),
+            });
+        }
+
+        Ok(records)
+    }
+
+    /// Scans the ledger for records owned by the given view key and decrypts them, fetching
+    /// several batches of blocks at once.
+    ///
+    /// [AleoAPIClient::scan_records] fetches one batch of 50 blocks at a time, so the first sync of
+    /// an account is bound by the latency of the node. This splits the block range into the same
+    /// batches and spreads them over a thread pool with the given number of workers, each fetching
+    /// a batch and decrypting its records, so up to `num_workers` requests are sent to the node at
+    /// once. Records are returned in the order they appear in the ledger, as with a serial scan.
+    pub fn scan_records_parallel(
+        &self,
+        view_key: impl TryInto<ViewKey<N>>,
+        block_heights: Range<u32>,
+        num_workers: usize,
+    ) -> Result<Vec<(Field<N>, Record<N, Plaintext<N>>)>>
+    where
+        T: Sync,
+    {
+        // Prepare the view key.
+        let view_key = view_key.try_into().map_err(|_| anyhow!("Invalid view key"))?;
+
+        ensure!(
+            block_heights.start < block_heights.end,
+            "The start block height must be less than the end block height"
+        );
+        ensure!(num_workers > 0, "The number of workers scanning blocks must be greater than 0");
+        let thread_pool = ThreadPoolBuilder::new().num_threads(num_workers).build()?;
+
+        let batches =
+            block_heights.clone().step_by(50).map(|start| start..block_heights.end.min(start + 50)).collect::<Vec<_>>();
+        let records = thread_pool.install(|| {
+            batches
+                .into_par_iter()
+                .map(|batch| {
+                    println!("Searching blocks {} to {} for records...", batch.start, batch.end);
+                    let ciphertexts = self
+                        .get_blocks(batch.start, batch.end)?
+                        .into_iter()
+                        .flat_map(|block| block.into_records())
+                        .collect();
+                    Ok(Self::decrypt_owned_records(&view_key, ciphertexts))
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+
+        Ok(records.into_iter().flatten().collect())
+    }
+
+    /// Get the transitions of a program found in the given range of blocks (end exclusive)
+    ///
+    /// Blocks are fetched 50 at a time and only the transitions belonging to the program are
+    /// kept, so memory use is bounded by the matching transitions rather than the range scanned
+    pub fn program_transitions(
+        &self,
+        program_id: impl TryInto<ProgramID<N>>,
+        start_height: u32,
+        end_height: u32,
+    ) -> Result<Vec<Transition<N>>> {
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        ensure!(start_height < end_height, "The start block height must be less than the end block height");
+
+        // Initialize a vector for the transitions.
+        let mut transitions = Vec::new();
+
+        for start in (start_height..end_height).step_by(50) {
+            let end = end_height.min(start + 50);
+            transitions.extend(
+                self.get_blocks(start, end)?
+                    .into_iter()
+                    .flat_map(|block| block.into_transitions())
+                    .filter(|transition| transition.program_id() == &program_id),
+            );
+        }
+
+        Ok(transitions)
+    }
+
+    /// Find which of the given record tags were spent in the given range of blocks (end exclusive)
+    ///
+    /// Tags can be computed from a view key with [crate::ProgramManager::compute_record_tags], so
+    /// this lets wallets without the private key check whether their records were spent. Nodes
+    /// can't be queried by tag, so every block in the range is fetched and the tags of the
+    /// records spent in its transitions are compared with the given tags.
+    pub fn find_spent_tags(&self, tags: &[Field<N>], block_heights: Range<u32>) -> Result<Vec<Field<N>>> {
+        ensure!(
+            block_heights.start < block_heights.end,
+            "The start block height must be less than the end block height"
+        );
+
+        // Initialize a vector for the spent tags.
+        let mut spent_tags = Vec::new();
+
+        for start_height in block_heights.clone().step_by(50) {
+            if spent_tags.len() == tags.len() {
+                break;
+            }
+            let end_height = block_heights.end.min(start_height + 50);
+            println!("Searching blocks {} to {} for spent records...", start_height, end_height);
+            for block in self.get_blocks(start_height, end_height)? {
+                spent_tags.extend(
+                    block.transitions().flat_map(|transition| transition.tags()).filter(|tag| tags.contains(tag)),
+                );
+            }
+        }
+
+        Ok(spent_tags)
+    }
+
     /// Search for unspent records in the ledger
     pub fn get_unspent_records(
         &self,
@@ -250,38 +495,79 @@ impl<N: Network> AleoAPIClient<N> {
         Ok(records)
     }
 
-    /// Broadcast a deploy or execute transaction to the Aleo network
+    /// Broadcast a deploy or execute transaction to the Aleo network.
+    ///
+    /// If the broadcast receives no response, a [NoResponse] error is returned, since the node
+    /// may still have received the transaction.
     pub fn transaction_broadcast(&self, transaction: Transaction<N>) -> Result<String> {
-        let url = format!("{}/{}/transaction/broadcast", self.base_url, self.network_id);
-        match self.client.post(&url).send_json(&transaction) {
-            Ok(response) => match response.into_string() {
-                Ok(success_response) => Ok(success_response),
-                Err(error) => bail!("❌ Transaction response was malformed {}", error),
-            },
-            Err(error) => {
-                let error_message = match error {
-                    ureq::Error::Status(code, response) => {
-                        format!("(status code {code}: {:?})", response.into_string()?)
-                    }
-                    ureq::Error::Transport(err) => format!("({err})"),
-                };
-
-                match transaction {
-                    Transaction::Deploy(..) => {
-                        bail!("❌ Failed to deploy program to {}: {}", &url, error_message)
-                    }
-                    Transaction::Execute(..) => {
-                        bail!("❌ Failed to broadcast execution to {}: {}", &url, error_message)
-                    }
-                }
+        let url = format!("{}/{}/transaction/broadcast", self.broadcast_url(), self.network_id);
+        let body = Self::transaction_body(&transaction);
+        if let Some(max_transaction_size) = self.max_transaction_size {
+            let size = body.len();
+            ensure!(
+                size <= max_transaction_size,
+                "❌ Transaction {} is {size} bytes, which is larger than the limit of {max_transaction_size} bytes of {}",
+                transaction.id(),
+                self.broadcast_url()
+            );
+        }
+        let error_message = match self.post(&url, &body) {
+            Ok(response) if response.is_success() => return Ok(response.body),
+            Ok(response) => format!("(status code {}: {:?})", response.status, response.body),
+            Err(error) => return Err(NoResponse { url, reason: error.to_string() }.into()),
+        };
+
+        match transaction {
+            Transaction::Deploy(..) => {
+                bail!("❌ Failed to deploy program to {}: {}", &url, error_message)
+            }
+            Transaction::Execute(..) => {
+                bail!("❌ Failed to broadcast execution to {}: {}", &url, error_message)
             }
         }
     }
+
+    // Serialize a transaction into the JSON body it is broadcast in
+    pub(crate) fn transaction_body(transaction: &Transaction<N>) -> String {
+        transaction.to_string()
+    }
+
+    // Decrypt the records owned by a view key in parallel, keeping the order of the records
+    fn decrypt_owned_records(
+        view_key: &ViewKey<N>,
+        records: Vec<(Field<N>, Record<N, Ciphertext<N>>)>,
+    ) -> Vec<(Field<N>, Record<N, Plaintext<N>>)> {
+        let address_x_coordinate = view_key.to_address().to_x_coordinate();
+        records
+            .into_par_iter()
+            .filter(|(_, record)| record.is_owner_with_address_x_coordinate(view_key, &address_x_coordinate))
+            .filter_map(|(commitment, record)| Some((commitment, record.decrypt(view_key).ok()?)))
+            .collect()
+    }
+
+    // Send a GET request, failing if the response is unsuccessful
+    fn get(&self, url: &str) -> Result<HttpResponse> {
+        let response = self.transport.request(HttpMethod::Get, url, None, self.timeout)?;
+        ensure!(response.is_success(), "{url}: status code {}: {}", response.status, response.body);
+        Ok(response)
+    }
+
+    // Send a POST request with a JSON body
+    fn post(&self, url: &str, body: &str) -> Result<HttpResponse> {
+        self.transport.request(HttpMethod::Post, url, Some(body), self.timeout)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{MockTransport, EXECUTE_TRANSACTION, RECIPIENT_PRIVATE_KEY};
+
+    use snarkvm::{parameters::testnet3::GenesisBytes, synthesizer::Transactions};
+    use snarkvm_console::{
+        prelude::{FromBytes, Uniform},
+        types::Scalar,
+    };
 
     #[test]
     fn test_api_get_blocks() {
@@ -297,4 +583,251 @@ mod tests {
         assert_eq!(blocks[1].previous_hash(), blocks[0].hash());
         assert_eq!(blocks[2].previous_hash(), blocks[1].hash());
     }
+
+    #[test]
+    fn test_api_program_transitions_rejects_invalid_input() {
+        let client = AleoAPIClient::<Testnet3>::local_testnet3("3030");
+
+        // Ensure invalid program IDs and empty block ranges are rejected before querying the network
+        assert!(client.program_transitions("credits", 0, 50).is_err());
+        assert!(client.program_transitions("credits.aleo", 50, 50).is_err());
+        assert!(client.program_transitions("credits.aleo", 100, 50).is_err());
+    }
+
+    #[test]
+    fn test_api_get_program_by_deployment() {
+        let transaction_id = "at1anuvpv3a8aven96y438386ncxfucxe8p8fjpwhlg950rzx8cusyquzauxp";
+        let transport = MockTransport::new().with_response(
+            HttpMethod::Get,
+            &format!("http://localhost:3030/testnet3/transaction/{transaction_id}"),
+            200,
+            EXECUTE_TRANSACTION,
+        );
+        let client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+
+        // Ensure transactions which aren't deployments are rejected
+        let error = client.get_program_by_deployment(FromStr::from_str(transaction_id).unwrap()).unwrap_err();
+        assert!(error.to_string().contains("did not deploy a program"));
+    }
+
+    #[test]
+    fn test_api_get_transaction_inclusion_proof() {
+        let block = Block::<Testnet3>::from_bytes_le(GenesisBytes::load_bytes()).unwrap();
+        let transaction_id = block.transaction_ids().next().copied().unwrap();
+        let transport = MockTransport::new()
+            .with_response(
+                HttpMethod::Get,
+                &format!("http://localhost:3030/testnet3/find/blockHash/{transaction_id}"),
+                200,
+                &serde_json::to_string(&block.hash()).unwrap(),
+            )
+            .with_response(
+                HttpMethod::Get,
+                &format!("http://localhost:3030/testnet3/block/{}", block.hash()),
+                200,
+                &serde_json::to_string(&block).unwrap(),
+            );
+        let client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+
+        // Ensure the proof verifies against the header of the block and only for its transaction
+        let proof = client.get_transaction_inclusion_proof(transaction_id).unwrap();
+        assert_eq!(proof.block_hash(), block.hash());
+        assert!(proof.verify(block.header()));
+        let other_transaction_id =
+            FromStr::from_str("at1anuvpv3a8aven96y438386ncxfucxe8p8fjpwhlg950rzx8cusyquzauxp").unwrap();
+        assert!(!InclusionProof::new(other_transaction_id, block.hash(), proof.path().clone()).verify(block.header()));
+    }
+
+    #[test]
+    fn test_api_is_serial_number_spent() {
+        let transition_id = "as1k9grqdjtrzk5nanmvalyrhhwjzktu5x4g2s094397dds4p7umqxsh68nsz";
+        let url = |serial_number: u64| format!("http://localhost:3030/testnet3/find/transitionID/{serial_number}field");
+        let transport = MockTransport::new()
+            .with_response(HttpMethod::Get, &url(1), 200, &format!("\"{transition_id}\""))
+            .with_response(HttpMethod::Get, &url(2), 404, "Not Found")
+            .with_response(
+                HttpMethod::Get,
+                &url(3),
+                500,
+                "Something went wrong: Failed to find the transition ID for the given input or output ID '3field'",
+            )
+            .with_response(HttpMethod::Get, &url(4), 500, "Something went wrong: storage is unavailable");
+        let client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+
+        // Ensure serial numbers the node can't find are unspent and other failures are errors
+        assert!(client.is_serial_number_spent(Field::from_u64(1)).unwrap());
+        assert!(!client.is_serial_number_spent(Field::from_u64(2)).unwrap());
+        assert!(!client.is_serial_number_spent(Field::from_u64(3)).unwrap());
+        assert!(client.is_serial_number_spent(Field::from_u64(4)).is_err());
+    }
+
+    #[test]
+    fn test_api_find_spent_tags() {
+        let transport = MockTransport::new().with_response(
+            HttpMethod::Get,
+            "http://localhost:3030/testnet3/blocks?start=0&end=10",
+            200,
+            "[]",
+        );
+        let client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+        let tags = [Field::from_str("1field").unwrap()];
+
+        // Ensure tags aren't reported as spent if no block spends them
+        assert!(client.find_spent_tags(&tags, 0..10).unwrap().is_empty());
+
+        // Ensure empty block ranges are rejected and failed block requests are errors
+        assert!(client.find_spent_tags(&tags, 10..10).is_err());
+        assert!(client.find_spent_tags(&tags, 10..20).is_err());
+    }
+
+    // Create an encrypted record holding the given amount of microcredits for an address
+    fn encrypted_record(address: &Address<Testnet3>, microcredits: u64) -> Record<Testnet3, Ciphertext<Testnet3>> {
+        let randomizer = Scalar::<Testnet3>::rand(&mut rand::thread_rng());
+        let nonce = Testnet3::g_scalar_multiply(&randomizer);
+        let record = Record::<Testnet3, Plaintext<Testnet3>>::from_str(&format!(
+            "{{ owner: {address}.private, microcredits: {microcredits}u64.private, _nonce: {nonce}.public }}"
+        ))
+        .unwrap();
+        record.encrypt(randomizer).unwrap()
+    }
+
+    #[test]
+    fn test_decrypt_owned_records_keeps_ledger_order() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+        let other_address = Address::try_from(PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap()).unwrap();
+
+        // Interleave records owned by the view key with records owned by another address
+        let records = (0..16u64)
+            .map(|i| {
+                let owner = if i % 3 == 0 { &other_address } else { &address };
+                (Field::from_u64(i), encrypted_record(owner, i))
+            })
+            .collect::<Vec<_>>();
+
+        // Ensure only the owned records are decrypted and they stay in order on any number of threads
+        let expected = (0..16u64).filter(|i| i % 3 != 0).collect::<Vec<_>>();
+        for threads in [1, 2, 4] {
+            let thread_pool = ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            let decrypted =
+                thread_pool.install(|| AleoAPIClient::<Testnet3>::decrypt_owned_records(&view_key, records.clone()));
+            assert_eq!(
+                decrypted.iter().map(|(commitment, _)| *commitment).collect::<Vec<_>>(),
+                expected.iter().map(|i| Field::from_u64(*i)).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                decrypted.iter().map(|(_, record)| record.microcredits().unwrap()).collect::<Vec<_>>(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_api_scan_records() {
+        let transport = MockTransport::new()
+            .with_response(HttpMethod::Get, "http://localhost:3030/testnet3/blocks?start=0&end=50", 200, "[]")
+            .with_response(HttpMethod::Get, "http://localhost:3030/testnet3/blocks?start=50&end=60", 200, "[]");
+        let client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+
+        // Ensure nothing is found in empty blocks, with and without a dedicated thread pool
+        assert!(client.scan_records(private_key, 0..60, None).unwrap().is_empty());
+        assert!(client.scan_records(private_key, 0..60, Some(2)).unwrap().is_empty());
+
+        // Ensure empty block ranges and thread pools without threads are rejected
+        assert!(client.scan_records(private_key, 60..60, None).is_err());
+        assert!(client.scan_records(private_key, 0..60, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_api_scan_records_parallel() {
+        // Create a block holding a transaction which outputs a record owned by the recipient
+        let genesis = Block::<Testnet3>::from_bytes_le(GenesisBytes::load_bytes()).unwrap();
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let block = Block::new(
+            &private_key,
+            genesis.hash(),
+            *genesis.header(),
+            Transactions::from(&[transaction]),
+            None,
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        let blocks = serde_json::to_string(&[&block]).unwrap();
+        let url = |start, end| format!("http://localhost:3030/testnet3/blocks?start={start}&end={end}");
+        let transport = MockTransport::new()
+            .with_response(HttpMethod::Get, &url(0, 50), 200, &blocks)
+            .with_response(HttpMethod::Get, &url(50, 100), 200, "[]")
+            .with_response(HttpMethod::Get, &url(100, 150), 200, &blocks)
+            .with_response(HttpMethod::Get, &url(150, 170), 200, &blocks);
+        let client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+
+        // Ensure scanning in parallel finds the same records in the same order as a serial scan
+        let serial = client.scan_records(private_key, 0..170, None).unwrap();
+        assert_eq!(serial.len(), 3);
+        for num_workers in [1, 2, 4, 8] {
+            assert_eq!(client.scan_records_parallel(private_key, 0..170, num_workers).unwrap(), serial);
+        }
+
+        // Ensure empty block ranges, pools without workers and failed block requests are rejected
+        assert!(client.scan_records_parallel(private_key, 60..60, 2).is_err());
+        assert!(client.scan_records_parallel(private_key, 0..170, 0).is_err());
+        assert!(client.scan_records_parallel(private_key, 0..200, 2).is_err());
+    }
+
+    #[test]
+    fn test_api_transaction_broadcast_size_limit() {
+        let transaction = Transaction::<Testnet3>::from_str(crate::test_utils::EXECUTE_TRANSACTION).unwrap();
+        let size = serde_json::to_string(&transaction).unwrap().len();
+        assert_ne!(size, transaction.to_bytes_le().unwrap().len());
+        let transport = MockTransport::new().with_response(
+            HttpMethod::Post,
+            "http://localhost:3030/testnet3/transaction/broadcast",
+            200,
+            &transaction.id().to_string(),
+        );
+        let client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+
+        // Ensure transactions up to the limit are broadcast and larger ones are rejected before being sent
+        assert!(client.with_max_transaction_size(Some(size)).transaction_broadcast(transaction.clone()).is_ok());
+        let error = client.with_max_transaction_size(Some(size - 1)).transaction_broadcast(transaction).unwrap_err();
+        assert!(error.to_string().contains(&format!("is {size} bytes")));
+    }
+
+    #[test]
+    fn test_api_transaction_broadcast_url() {
+        let transaction = Transaction::<Testnet3>::from_str(crate::test_utils::EXECUTE_TRANSACTION).unwrap();
+        let transport = MockTransport::new()
+            .with_response(HttpMethod::Get, "http://localhost:3030/testnet3/latest/height", 200, "5")
+            .with_response(
+                HttpMethod::Post,
+                "http://localhost:3032/testnet3/transaction/broadcast",
+                200,
+                &transaction.id().to_string(),
+            );
+        let client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+        assert_eq!(client.broadcast_url(), "http://localhost:3030");
+
+        // Ensure transactions are broadcast to the broadcast node while queries go to the base url
+        let split_client = client.with_broadcast_url(Some("http://localhost:3032")).unwrap();
+        assert_eq!(split_client.broadcast_url(), "http://localhost:3032");
+        assert_eq!(split_client.base_url(), "http://localhost:3030");
+        assert!(split_client.transaction_broadcast(transaction.clone()).is_ok());
+        assert_eq!(split_client.latest_height().unwrap(), 5);
+        assert!(client.transaction_broadcast(transaction).is_err());
+
+        // Ensure invalid broadcast urls are rejected
+        assert!(client.with_broadcast_url(Some("localhost:3032")).is_err());
+        assert_eq!(split_client.with_broadcast_url(None).unwrap().broadcast_url(), "http://localhost:3030");
+    }
 }
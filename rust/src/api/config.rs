@@ -0,0 +1,390 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{fs, path::Path};
+
+/// Network settings used to create an [AleoAPIClient].
+///
+/// The config can be stored declaratively in a JSON or TOML file. Either the `base_url` or the
+/// `broadcast_url` is required: queries are sent to the `base_url`, which can also be written as
+/// `query_url`, and transactions are broadcast to the `broadcast_url`, with the node of whichever
+/// url is set used for both if only one is. The `network_id` defaults to testnet3 and requests
+/// never time out unless `timeout_secs` is set:
+/// ```toml
+/// base_url = "http://localhost:3030"
+/// broadcast_url = "http://localhost:3032"
+/// network_id = "testnet3"
+/// timeout_secs = 30
+/// max_transaction_size = 1048576
+/// parameters_dir = "/opt/aleo/parameters"
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "NetworkConfigFields")]
+pub struct NetworkConfig {
+    /// The base URL of the REST API of the node queried by the client
+    pub base_url: String,
+    /// The base URL of the REST API of the node transactions are broadcast to, if it isn't the
+    /// node at the `base_url`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broadcast_url: Option<String>,
+    /// The name of the network the node serves, used as the first segment of every endpoint
+    pub network_id: String,
+    /// The default timeout in seconds for each request to the node, requests never time out if it
+    /// is not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// The size in bytes of the largest transaction the node accepts, measured as the JSON body
+    /// it is broadcast in. Transactions of any size are broadcast if it is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_transaction_size: Option<usize>,
+    /// A directory holding a local copy of the universal parameters and proving parameters used
+    /// to build transactions, see [NetworkConfig::install_parameters]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters_dir: Option<PathBuf>,
+}
+
+// The fields of a network config as they are written in a config file, where either url can be
+// left out
+#[derive(Deserialize)]
+struct NetworkConfigFields {
+    #[serde(default, alias = "query_url")]
+    base_url: Option<String>,
+    #[serde(default)]
+    broadcast_url: Option<String>,
+    #[serde(default = "NetworkConfig::default_network_id")]
+    network_id: String,
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+    #[serde(default)]
+    max_transaction_size: Option<usize>,
+    #[serde(default)]
+    parameters_dir: Option<PathBuf>,
+}
+
+impl TryFrom<NetworkConfigFields> for NetworkConfig {
+    type Error = String;
+
+    fn try_from(fields: NetworkConfigFields) -> Result<Self, Self::Error> {
+        let (base_url, broadcast_url) = match (fields.base_url, fields.broadcast_url) {
+            (Some(base_url), broadcast_url) => (base_url, broadcast_url),
+            (None, Some(broadcast_url)) => (broadcast_url, None),
+            (None, None) => return Err("❌ A network config needs a base_url or a broadcast_url".to_string()),
+        };
+        Ok(Self {
+            base_url,
+            broadcast_url,
+            network_id: fields.network_id,
+            timeout_secs: fields.timeout_secs,
+            max_transaction_size: fields.max_transaction_size,
+            parameters_dir: fields.parameters_dir,
+        })
+    }
+}
+
+impl NetworkConfig {
+    /// Create a network config for a node at the given base URL serving the given network
+    pub fn new(base_url: &str, network_id: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            broadcast_url: None,
+            network_id: network_id.to_string(),
+            timeout_secs: None,
+            max_transaction_size: None,
+            parameters_dir: None,
+        }
+    }
+
+    /// Broadcast transactions to the node at the given base URL instead of the node at the
+    /// `base_url`, i.e. when reads and writes are served by separate nodes
+    pub fn with_broadcast_url(mut self, broadcast_url: &str) -> Self {
+        self.broadcast_url = Some(broadcast_url.to_string());
+        self
+    }
+
+    /// Set the default timeout in seconds for each request to the node
+    pub fn with_timeout_secs(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+
+    /// Set the size in bytes of the largest transaction the node accepts
+    pub fn with_max_transaction_size(mut self, max_transaction_size: usize) -> Self {
+        self.max_transaction_size = Some(max_transaction_size);
+        self
+    }
+
+    /// Load the parameters used to build transactions from a local directory instead of
+    /// downloading them once they are installed with [NetworkConfig::install_parameters], which
+    /// [crate::ProgramManager::new_account] and [crate::ProgramManager::read_only] call for
+    /// their config
+    pub fn with_parameters_dir(mut self, parameters_dir: impl Into<PathBuf>) -> Self {
+        self.parameters_dir = Some(parameters_dir.into());
+        self
+    }
+
+    /// Make the parameter files found in the parameters directory available to snarkVM,
+    /// returning the number of files installed.
+    ///
+    /// snarkVM loads the universal SRS and the proving parameters of credits.aleo from
+    /// `~/.aleo/resources` the first time a VM needs them, downloading the files missing from
+    /// that directory from <https://testnet3.parameters.aleo.org>. Downloading them takes a long
+    /// time, so machines which start from a clean home directory, i.e. containers, can point the
+    /// config at a directory holding copies of these files, for example a copy of
+    /// `~/.aleo/resources` from a machine which already downloaded them, and call this method
+    /// before building transactions. [AleoAPIClient::from_config] doesn't install them, while
+    /// the program managers created from a config do.
+    ///
+    /// snarkVM names these files after the first characters of their SHA-256 checksum, i.e.
+    /// `transfer.prover.837ad21`. A file whose checksum doesn't match its name is rejected. The
+    /// files are copied into snarkVM's resource directory unless a file with the same checksum is
+    /// already there.
+    pub fn install_parameters(&self) -> Result<usize> {
+        match &self.parameters_dir {
+            Some(parameters_dir) => Self::copy_parameters(parameters_dir, &aleo_std::aleo_dir().join("resources")),
+            None => Ok(0),
+        }
+    }
+
+    // Copy the files of a parameters directory into a resource directory, skipping the files
+    // which are already present with the same checksum
+    fn copy_parameters(parameters_dir: &Path, resources_dir: &Path) -> Result<usize> {
+        ensure!(parameters_dir.is_dir(), "Parameters directory {parameters_dir:?} does not exist");
+        fs::create_dir_all(resources_dir)?;
+        let mut installed = 0;
+        for entry in fs::read_dir(parameters_dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            let path = entry.path();
+            let checksum = Self::checksum(&path)?;
+            let expected_checksum = path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .filter(|extension| extension.len() == 7 && extension.chars().all(|c| c.is_ascii_hexdigit()));
+            if let Some(expected_checksum) = expected_checksum {
+                ensure!(
+                    checksum.starts_with(&expected_checksum.to_ascii_lowercase()),
+                    "Parameter file {path:?} has the checksum {checksum}, which doesn't match its name"
+                );
+            }
+            let destination = resources_dir.join(entry.file_name());
+            if !destination.is_file() || Self::checksum(&destination)? != checksum {
+                fs::copy(&path, &destination)?;
+                installed += 1;
+            }
+        }
+        Ok(installed)
+    }
+
+    // Compute the SHA-256 checksum of a file as a hex string
+    fn checksum(path: &Path) -> Result<String> {
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Network config for the public testnet3 API
+    pub fn testnet3() -> Self {
+        Self::new("https://vm.aleo.org/api", "testnet3")
+    }
+
+    /// Network config for a testnet3 node running locally on the given port
+    pub fn local_testnet3(port: &str) -> Self {
+        Self::new(&format!("http://localhost:{port}"), "testnet3")
+    }
+
+    fn default_network_id() -> String {
+        "testnet3".to_string()
+    }
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self::testnet3()
+    }
+}
+
+impl<N: Network> AleoAPIClient<N> {
+    /// Create an API client from a network config
+    pub fn from_config(config: &NetworkConfig) -> Result<Self> {
+        Self::from_config_with_transport(config, UreqTransport::new())
+    }
+}
+
+impl<N: Network, T: HttpTransport> AleoAPIClient<N, T> {
+    /// Create an API client from a network config which sends its requests through the given
+    /// transport. The parameters of the config aren't installed, see
+    /// [NetworkConfig::install_parameters].
+    pub fn from_config_with_transport(config: &NetworkConfig, transport: T) -> Result<Self> {
+        let api_client = Self::with_transport(&config.base_url, &config.network_id, transport)?;
+        Ok(api_client
+            .with_broadcast_url(config.broadcast_url.as_deref())?
+            .with_timeout(config.timeout_secs.map(Duration::from_secs))
+            .with_max_transaction_size(config.max_transaction_size))
+    }
+
+    /// Get the network config of the API client. The parameters directory is not part of the
+    /// client, as the parameters are installed separately.
+    pub fn network_config(&self) -> NetworkConfig {
+        NetworkConfig {
+            broadcast_url: self.broadcast_url.clone(),
+            timeout_secs: self.timeout.map(|timeout| timeout.as_secs()),
+            max_transaction_size: self.max_transaction_size,
+            ..NetworkConfig::new(&self.base_url, &self.network_id)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_network_config_from_toml() {
+        let config: NetworkConfig = toml::from_str(
+            r#"
+            base_url = "http://localhost:3030"
+            network_id = "testnet3"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config, NetworkConfig::local_testnet3("3030"));
+
+        // Ensure the config survives a round trip through TOML
+        let serialized = toml::to_string(&config).unwrap();
+        assert_eq!(toml::from_str::<NetworkConfig>(&serialized).unwrap(), config);
+
+        // Ensure optional fields fall back to their defaults
+        let config: NetworkConfig = toml::from_str(r#"base_url = "https://vm.aleo.org/api""#).unwrap();
+        assert_eq!(config, NetworkConfig::testnet3());
+
+        // Ensure a parameters directory can be configured
+        let config: NetworkConfig = toml::from_str(
+            r#"
+            base_url = "http://localhost:3030"
+            parameters_dir = "/opt/aleo/parameters"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config, NetworkConfig::local_testnet3("3030").with_parameters_dir("/opt/aleo/parameters"));
+
+        // Ensure a separate broadcast node can be configured, with the base url written as the
+        // query url
+        let config: NetworkConfig = toml::from_str(
+            r#"
+            query_url = "http://localhost:3030"
+            broadcast_url = "http://localhost:3032"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config, NetworkConfig::local_testnet3("3030").with_broadcast_url("http://localhost:3032"));
+
+        // Ensure the node of the broadcast url is queried as well if it is the only url
+        let config: NetworkConfig = toml::from_str(r#"broadcast_url = "http://localhost:3032""#).unwrap();
+        assert_eq!(config, NetworkConfig::local_testnet3("3032"));
+
+        // Ensure a config without a url is rejected
+        let error = toml::from_str::<NetworkConfig>(r#"network_id = "testnet3""#).unwrap_err();
+        assert!(error.to_string().contains("needs a base_url or a broadcast_url"));
+    }
+
+    #[test]
+    fn test_api_client_from_network_config() {
+        let config = NetworkConfig::local_testnet3("3030");
+        let api_client = AleoAPIClient::<Testnet3>::from_config(&config).unwrap();
+        assert_eq!(api_client.base_url(), "http://localhost:3030");
+        assert_eq!(api_client.network_id(), "testnet3");
+        assert_eq!(api_client.network_config(), config);
+        assert_eq!(api_client.timeout(), None);
+
+        // Ensure the timeout of the config is applied and can be overridden for a single request
+        let config = config.with_timeout_secs(30);
+        let api_client = AleoAPIClient::<Testnet3>::from_config(&config).unwrap();
+        assert_eq!(api_client.timeout(), Some(Duration::from_secs(30)));
+        assert_eq!(api_client.network_config(), config);
+        let quick_client = api_client.with_timeout(Some(Duration::from_millis(500)));
+        assert_eq!(quick_client.timeout(), Some(Duration::from_millis(500)));
+        assert_eq!(quick_client.base_url(), api_client.base_url());
+        assert_eq!(api_client.timeout(), Some(Duration::from_secs(30)));
+
+        // Ensure the transaction size limit of the config is applied
+        let config = NetworkConfig::local_testnet3("3030").with_max_transaction_size(1024);
+        let api_client = AleoAPIClient::<Testnet3>::from_config(&config).unwrap();
+        assert_eq!(api_client.max_transaction_size(), Some(1024));
+        assert_eq!(api_client.network_config(), config);
+
+        // Ensure the broadcast url of the config is applied
+        let config = NetworkConfig::local_testnet3("3030").with_broadcast_url("http://localhost:3032");
+        let api_client = AleoAPIClient::<Testnet3>::from_config(&config).unwrap();
+        assert_eq!(api_client.base_url(), "http://localhost:3030");
+        assert_eq!(api_client.broadcast_url(), "http://localhost:3032");
+        assert_eq!(api_client.network_config(), config);
+
+        // Ensure configs with invalid urls are rejected
+        assert!(AleoAPIClient::<Testnet3>::from_config(&NetworkConfig::new("localhost:3030", "testnet3")).is_err());
+        let config = NetworkConfig::local_testnet3("3030").with_broadcast_url("localhost:3032");
+        assert!(AleoAPIClient::<Testnet3>::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_copy_parameters() {
+        let parameters_dir = std::env::temp_dir().join("aleo_test_copy_parameters_source");
+        let resources_dir = std::env::temp_dir().join("aleo_test_copy_parameters_resources");
+        let _ = fs::remove_dir_all(&parameters_dir);
+        let _ = fs::remove_dir_all(&resources_dir);
+        fs::create_dir_all(parameters_dir.join("nested")).unwrap();
+        fs::write(parameters_dir.join("powers-of-beta-16.usrs"), [1u8; 16]).unwrap();
+        let prover = parameters_dir.join("transfer.prover");
+        fs::write(&prover, [2u8; 8]).unwrap();
+        let prover_name = format!("transfer.prover.{}", &NetworkConfig::checksum(&prover).unwrap()[..7]);
+        fs::rename(&prover, parameters_dir.join(&prover_name)).unwrap();
+
+        // Ensure files are copied once and directories are skipped
+        assert_eq!(NetworkConfig::copy_parameters(&parameters_dir, &resources_dir).unwrap(), 2);
+        assert_eq!(fs::read(resources_dir.join("powers-of-beta-16.usrs")).unwrap(), [1u8; 16]);
+        assert!(!resources_dir.join("nested").exists());
+        assert_eq!(NetworkConfig::copy_parameters(&parameters_dir, &resources_dir).unwrap(), 0);
+
+        // Ensure partially written files are replaced
+        fs::write(resources_dir.join(&prover_name), [2u8; 3]).unwrap();
+        assert_eq!(NetworkConfig::copy_parameters(&parameters_dir, &resources_dir).unwrap(), 1);
+        assert_eq!(fs::read(resources_dir.join(&prover_name)).unwrap(), [2u8; 8]);
+
+        // Ensure corrupted files of the right size are replaced
+        fs::write(resources_dir.join(&prover_name), [3u8; 8]).unwrap();
+        assert_eq!(NetworkConfig::copy_parameters(&parameters_dir, &resources_dir).unwrap(), 1);
+        assert_eq!(fs::read(resources_dir.join(&prover_name)).unwrap(), [2u8; 8]);
+
+        // Ensure files which don't match the checksum in their name are rejected
+        fs::write(parameters_dir.join(&prover_name), [3u8; 8]).unwrap();
+        let error = NetworkConfig::copy_parameters(&parameters_dir, &resources_dir).unwrap_err();
+        assert!(error.to_string().contains("doesn't match its name"));
+        fs::remove_file(parameters_dir.join(&prover_name)).unwrap();
+
+        // Ensure missing parameter directories are reported and configs without one install nothing
+        assert!(NetworkConfig::copy_parameters(&parameters_dir.join("missing"), &resources_dir).is_err());
+        assert_eq!(NetworkConfig::testnet3().install_parameters().unwrap(), 0);
+
+        fs::remove_dir_all(&parameters_dir).unwrap();
+        fs::remove_dir_all(&resources_dir).unwrap();
+    }
+}
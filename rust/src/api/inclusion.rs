@@ -0,0 +1,59 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::synthesizer::Header;
+use snarkvm_console::{prelude::ToBits, program::TransactionsPath};
+
+/// A Merkle proof that a transaction is included in a block, found with
+/// [AleoAPIClient::get_transaction_inclusion_proof].
+///
+/// The proof is a path from the transaction ID to the transactions root of the block, so it can
+/// be checked against a block header without the transactions of the block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InclusionProof<N: Network> {
+    transaction_id: N::TransactionID,
+    block_hash: N::BlockHash,
+    path: TransactionsPath<N>,
+}
+
+impl<N: Network> InclusionProof<N> {
+    /// Create a proof that a transaction is included in the block with the given hash
+    pub fn new(transaction_id: N::TransactionID, block_hash: N::BlockHash, path: TransactionsPath<N>) -> Self {
+        Self { transaction_id, block_hash, path }
+    }
+
+    /// Get the ID of the transaction the proof is for
+    pub fn transaction_id(&self) -> N::TransactionID {
+        self.transaction_id
+    }
+
+    /// Get the hash of the block the transaction is claimed to be included in
+    pub fn block_hash(&self) -> N::BlockHash {
+        self.block_hash
+    }
+
+    /// Get the Merkle path from the transaction ID to the transactions root of the block
+    pub fn path(&self) -> &TransactionsPath<N> {
+        &self.path
+    }
+
+    /// Determine if the proof shows the transaction is included in the block with the given header
+    pub fn verify(&self, header: &Header<N>) -> bool {
+        N::verify_merkle_path_bhp(&self.path, &header.transactions_root(), &self.transaction_id.to_bits_le())
+    }
+}
@@ -18,39 +18,73 @@
 
 use super::*;
 
+use std::time::Duration;
+
 pub mod blocking;
 pub use blocking::*;
 
+pub mod config;
+pub use config::*;
+
+pub mod inclusion;
+pub use inclusion::*;
+
+pub mod scan;
+pub use scan::*;
+
+pub mod subscription;
+pub use subscription::*;
+
+pub mod transport;
+pub use transport::*;
+
 /// Aleo API client for interacting with the Aleo Beacon API
+///
+/// Requests are sent through an [HttpTransport], which is [UreqTransport] unless another
+/// transport is supplied with [AleoAPIClient::with_transport].
 #[derive(Clone, Debug)]
-pub struct AleoAPIClient<N: Network> {
-    client: ureq::Agent,
+pub struct AleoAPIClient<N: Network, T: HttpTransport = UreqTransport> {
+    transport: T,
     base_url: String,
+    broadcast_url: Option<String>,
     network_id: String,
+    timeout: Option<Duration>,
+    max_transaction_size: Option<usize>,
     _network: PhantomData<N>,
 }
 
 impl<N: Network> AleoAPIClient<N> {
     pub fn new(base_url: &str, chain: &str) -> Result<Self> {
-        let client = ureq::Agent::new();
-        ensure!(
-            base_url.starts_with("http://") || base_url.starts_with("https://"),
-            "specified url {base_url} invalid, the base url must start with or https:// (or http:// if doing local development)"
-        );
+        Self::with_transport(base_url, chain, UreqTransport::new())
+    }
+
+    pub fn testnet3() -> Self {
+        Self::from_config(&NetworkConfig::testnet3()).unwrap()
+    }
+
+    pub fn local_testnet3(port: &str) -> Self {
+        Self::from_config(&NetworkConfig::local_testnet3(port)).unwrap()
+    }
+}
+
+impl<N: Network, T: HttpTransport> AleoAPIClient<N, T> {
+    /// Create an API client which sends its requests through the given transport
+    pub fn with_transport(base_url: &str, chain: &str, transport: T) -> Result<Self> {
+        Self::check_url(base_url)?;
         Ok(AleoAPIClient {
-            client,
+            transport,
             base_url: base_url.to_string(),
+            broadcast_url: None,
             network_id: chain.to_string(),
+            timeout: None,
+            max_transaction_size: None,
             _network: PhantomData,
         })
     }
 
-    pub fn testnet3() -> Self {
-        Self::new("https://vm.aleo.org/api", "testnet3").unwrap()
-    }
-
-    pub fn local_testnet3(port: &str) -> Self {
-        Self::new(&format!("http://localhost:{}", port), "testnet3").unwrap()
+    /// Get the transport requests are sent through
+    pub fn transport(&self) -> &T {
+        &self.transport
     }
 
     /// Get base URL
@@ -58,8 +92,62 @@ impl<N: Network> AleoAPIClient<N> {
         &self.base_url
     }
 
+    /// Get the base URL of the node transactions are broadcast to, which is the base URL unless
+    /// a separate broadcast node was set with [AleoAPIClient::with_broadcast_url]
+    pub fn broadcast_url(&self) -> &str {
+        self.broadcast_url.as_deref().unwrap_or(&self.base_url)
+    }
+
+    /// Get a copy of the API client which broadcasts transactions to the node at the given base
+    /// URL while every other request is still sent to its base URL, i.e. to query a read node and
+    /// broadcast through a validator. Passing `None` broadcasts to the base URL again.
+    pub fn with_broadcast_url(&self, broadcast_url: Option<&str>) -> Result<Self> {
+        if let Some(broadcast_url) = broadcast_url {
+            Self::check_url(broadcast_url)?;
+        }
+        Ok(Self { broadcast_url: broadcast_url.map(str::to_string), ..self.clone() })
+    }
+
     /// Get network ID being interacted with
     pub fn network_id(&self) -> &str {
         &self.network_id
     }
+
+    /// Get the timeout applied to each request, requests never time out if it is not set
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Get a copy of the API client which applies the given timeout to each request instead of
+    /// the default timeout of its network config.
+    ///
+    /// The copy shares the transport of the original client (and with it the connection pool of
+    /// the default transport), so it can be created cheaply for a single call, i.e. `api_client.with_timeout(Some(Duration::from_secs(5))).latest_height()`.
+    /// Passing `None` disables the timeout.
+    pub fn with_timeout(&self, timeout: Option<Duration>) -> Self {
+        Self { timeout, ..self.clone() }
+    }
+
+    /// Get the size in bytes of the JSON body of the largest transaction broadcast by the API
+    /// client, transactions of any size are broadcast if it is not set
+    pub fn max_transaction_size(&self) -> Option<usize> {
+        self.max_transaction_size
+    }
+
+    /// Get a copy of the API client which refuses to broadcast transactions whose JSON body is
+    /// larger than the given size in bytes, so transactions the node would reject are caught
+    /// before they are sent.
+    /// Passing `None` removes the limit.
+    pub fn with_max_transaction_size(&self, max_transaction_size: Option<usize>) -> Self {
+        Self { max_transaction_size, ..self.clone() }
+    }
+
+    // Ensure a base url uses a scheme the transport can send requests with
+    fn check_url(url: &str) -> Result<()> {
+        ensure!(
+            url.starts_with("http://") || url.starts_with("https://"),
+            "specified url {url} invalid, the base url must start with or https:// (or http:// if doing local development)"
+        );
+        Ok(())
+    }
 }
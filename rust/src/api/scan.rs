@@ -0,0 +1,115 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Progress of a record scan which can be persisted between scans.
+///
+/// The state records the commitment of every record which has been checked for ownership along
+/// with the highest block height scanned. Scans performed with the state skip records whose
+/// commitments have already been seen, so repeated syncs only process records that are new.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct ScanState<N: Network> {
+    latest_height: Option<u32>,
+    seen_commitments: HashSet<Field<N>>,
+}
+
+impl<N: Network> ScanState<N> {
+    /// Create an empty scan state
+    pub fn new() -> Self {
+        Self { latest_height: None, seen_commitments: HashSet::new() }
+    }
+
+    /// Get the highest block height scanned, if any blocks have been scanned
+    pub fn latest_height(&self) -> Option<u32> {
+        self.latest_height
+    }
+
+    /// Determine if a record commitment has already been seen by a scan
+    pub fn contains(&self, commitment: &Field<N>) -> bool {
+        self.seen_commitments.contains(commitment)
+    }
+
+    /// Get the number of record commitments seen by previous scans
+    pub fn len(&self) -> usize {
+        self.seen_commitments.len()
+    }
+
+    /// Determine if no record commitments have been seen yet
+    pub fn is_empty(&self) -> bool {
+        self.seen_commitments.is_empty()
+    }
+
+    // Record a commitment as seen, returning true if it had not been seen before
+    pub(crate) fn insert(&mut self, commitment: Field<N>) -> bool {
+        self.seen_commitments.insert(commitment)
+    }
+
+    // Advance the scan cursor to the given height if it is higher than the current one
+    pub(crate) fn update_height(&mut self, height: u32) {
+        self.latest_height = Some(self.latest_height.map_or(height, |latest| latest.max(height)));
+    }
+}
+
+impl<N: Network> Default for ScanState<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console::{network::Testnet3, prelude::TestRng};
+
+    #[test]
+    fn test_scan_state_tracks_commitments_and_height() {
+        let mut rng = TestRng::default();
+        let mut scan_state = ScanState::<Testnet3>::new();
+        assert!(scan_state.is_empty());
+        assert_eq!(scan_state.latest_height(), None);
+
+        // Ensure commitments are only reported as new the first time they are seen
+        let commitment = Field::<Testnet3>::rand(&mut rng);
+        assert!(scan_state.insert(commitment));
+        assert!(!scan_state.insert(commitment));
+        assert!(scan_state.contains(&commitment));
+        assert_eq!(scan_state.len(), 1);
+
+        // Ensure the scan cursor never moves backwards
+        scan_state.update_height(99);
+        scan_state.update_height(49);
+        assert_eq!(scan_state.latest_height(), Some(99));
+    }
+
+    #[test]
+    fn test_scan_state_serialization_round_trip() {
+        let mut rng = TestRng::default();
+        let mut scan_state = ScanState::<Testnet3>::new();
+        (0..10).for_each(|_| {
+            scan_state.insert(Field::rand(&mut rng));
+        });
+        scan_state.update_height(1000);
+
+        let serialized = serde_json::to_string(&scan_state).unwrap();
+        let deserialized: ScanState<Testnet3> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(scan_state, deserialized);
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::{collections::VecDeque, thread};
+
+// Time waited between polls of the latest block height when no new block is available
+const BLOCK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+// Number of recent block hashes kept to find where a reorganization starts
+const RECENT_BLOCKS: usize = 100;
+
+/// Iterator over new blocks at the tip of the chain, created with
+/// [AleoAPIClient::subscribe_blocks]
+///
+/// The iterator never ends, it waits for the next block to be added whenever it reaches the
+/// tip. Failures to query the node are yielded as errors and the next call polls the node again.
+///
+/// Reorganizations are detected when the chain becomes shorter than the blocks already yielded,
+/// when its tip is replaced or when a new block doesn't build on the previous block yielded. The
+/// subscription then steps back to the first height which changed and yields the blocks from
+/// there again, so a block at a height which was yielded before replaces the block previously
+/// yielded at that height.
+pub struct BlockSubscription<'a, N: Network, T: HttpTransport> {
+    api_client: &'a AleoAPIClient<N, T>,
+    next_height: Option<u32>,
+    recent_blocks: VecDeque<(u32, N::BlockHash)>,
+    poll_interval: Duration,
+}
+
+impl<'a, N: Network, T: HttpTransport> BlockSubscription<'a, N, T> {
+    /// Set the time waited between polls of the node when no new block is available
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Get the height of the next block the subscription will yield, once it has polled the node
+    pub fn next_height(&self) -> Option<u32> {
+        self.next_height
+    }
+
+    // Wait for the next block, stepping back to the start of any reorganization found
+    fn next_block(&mut self) -> Result<Block<N>> {
+        loop {
+            let latest_height = self.api_client.latest_height()?;
+            let next_height = *self.next_height.get_or_insert(latest_height + 1);
+
+            // The chain got shorter than the blocks already yielded, so the blocks above its tip
+            // were replaced and its tip is checked again in case it was replaced too
+            if latest_height + 1 < next_height {
+                println!("Chain reorganized, height regressed from {} to {latest_height}", next_height - 1);
+                self.recent_blocks.retain(|(height, _)| *height <= latest_height);
+                self.next_height = Some(latest_height);
+                continue;
+            }
+
+            if next_height <= latest_height {
+                let block = self.api_client.get_block(next_height)?;
+                // Skip blocks which were already yielded and weren't replaced, and forget the
+                // blocks which were
+                if let Some(index) = self.recent_blocks.iter().position(|(height, _)| *height == next_height) {
+                    if self.recent_blocks[index].1 == block.hash() {
+                        self.next_height = Some(next_height + 1);
+                        continue;
+                    }
+                    self.recent_blocks.truncate(index);
+                }
+                // A block which doesn't build on the previous block yielded belongs to a fork,
+                // so the previous block was replaced as well
+                if let Some((height, hash)) = self.recent_blocks.back() {
+                    if *height + 1 == next_height && block.previous_hash() != *hash {
+                        println!("Chain reorganized, block {height} was replaced");
+                        self.next_height = Some(*height);
+                        continue;
+                    }
+                }
+                self.recent_blocks.push_back((next_height, block.hash()));
+                if self.recent_blocks.len() > RECENT_BLOCKS {
+                    self.recent_blocks.pop_front();
+                }
+                self.next_height = Some(next_height + 1);
+                return Ok(block);
+            }
+
+            // A block replacing the tip doesn't change the height of the chain, so the hash of
+            // the tip is checked as well
+            if let Some((height, hash)) = self.recent_blocks.back() {
+                if *height == latest_height && self.api_client.latest_hash()? != *hash {
+                    println!("Chain reorganized, block {height} was replaced");
+                    self.next_height = Some(latest_height);
+                    continue;
+                }
+            }
+            thread::sleep(self.poll_interval);
+        }
+    }
+}
+
+impl<'a, N: Network, T: HttpTransport> Iterator for BlockSubscription<'a, N, T> {
+    type Item = Result<Block<N>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_block())
+    }
+}
+
+impl<N: Network, T: HttpTransport> AleoAPIClient<N, T> {
+    /// Subscribe to the blocks added to the chain, polling the node for new blocks at its tip.
+    ///
+    /// The first block yielded is the first block added after the subscription first polls the
+    /// node. See [BlockSubscription] for how reorganizations are handled.
+    pub fn subscribe_blocks(&self) -> BlockSubscription<'_, N, T> {
+        self.subscribe_blocks_from(None)
+    }
+
+    /// Subscribe to the blocks of the chain starting at the given height, i.e. to resume a
+    /// subscription from the next height it would have yielded. Blocks already in the chain are
+    /// yielded first, then blocks are yielded as they are added.
+    pub fn subscribe_blocks_from(&self, start_height: Option<u32>) -> BlockSubscription<'_, N, T> {
+        BlockSubscription {
+            api_client: self,
+            next_height: start_height,
+            recent_blocks: VecDeque::new(),
+            poll_interval: BLOCK_POLL_INTERVAL,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{EXECUTE_TRANSACTION, RECIPIENT_PRIVATE_KEY};
+
+    use snarkvm::{
+        parameters::testnet3::GenesisBytes,
+        synthesizer::{Header, Transactions},
+    };
+    use snarkvm_console::prelude::{FromBytes, Uniform, Zero};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+        Mutex,
+    };
+
+    // Transport serving the latest height and the blocks of a chain which can change between
+    // requests, counting the requests it served
+    #[derive(Clone, Debug, Default)]
+    struct ChainTransport {
+        chain: Arc<Mutex<Vec<Block<Testnet3>>>>,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl HttpTransport for ChainTransport {
+        fn request(
+            &self,
+            _method: HttpMethod,
+            url: &str,
+            _body: Option<&str>,
+            _timeout: Option<Duration>,
+        ) -> Result<HttpResponse> {
+            let chain = self.chain.lock().unwrap();
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let path = url.trim_start_matches("http://localhost:3030/testnet3/");
+            if path == "latest/height" {
+                return Ok(HttpResponse::new(200, &(chain.len() - 1).to_string()));
+            } else if path == "latest/hash" {
+                return Ok(HttpResponse::new(200, &serde_json::to_string(&chain.last().unwrap().hash()).unwrap()));
+            }
+            let block = path.strip_prefix("block/").and_then(|height| chain.get(height.parse::<usize>().ok()?));
+            Ok(block.map_or(HttpResponse::new(404, "Block not found"), |block| {
+                HttpResponse::new(200, &serde_json::to_string(block).unwrap())
+            }))
+        }
+    }
+
+    // Create a block building on the block with the given hash. Blocks are given a random
+    // transactions root, so each block has a different hash.
+    fn block_on(previous_hash: <Testnet3 as Network>::BlockHash) -> Block<Testnet3> {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+        let genesis = Block::<Testnet3>::from_bytes_le(GenesisBytes::load_bytes()).unwrap();
+        let rng = &mut rand::thread_rng();
+        let zero = Field::<Testnet3>::zero();
+        let header = Header::from(zero, Field::rand(rng), zero, zero, *genesis.header().metadata()).unwrap();
+        Block::new(&private_key, previous_hash, header, Transactions::from(&[transaction]), None, rng).unwrap()
+    }
+
+    // Add a block to the tip of the chain
+    fn push_block(chain: &Mutex<Vec<Block<Testnet3>>>) -> Block<Testnet3> {
+        let mut chain = chain.lock().unwrap();
+        let block = block_on(chain.last().unwrap().hash());
+        chain.push(block.clone());
+        block
+    }
+
+    #[test]
+    fn test_subscribe_blocks() {
+        let genesis = Block::<Testnet3>::from_bytes_le(GenesisBytes::load_bytes()).unwrap();
+        let transport = ChainTransport::default();
+        let chain = transport.chain.clone();
+        let requests = transport.requests.clone();
+        chain.lock().unwrap().extend([genesis.clone(), block_on(genesis.hash())]);
+        let client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+
+        // Ensure blocks added after the subscription starts are yielded in order. Blocks are only
+        // added once the subscription polled the node, since it starts after the tip it first sees.
+        let mut subscription = client.subscribe_blocks().with_poll_interval(Duration::from_millis(10));
+        assert_eq!(subscription.next_height(), None);
+        let producer_chain = chain.clone();
+        let producer = thread::spawn(move || {
+            while requests.load(Ordering::SeqCst) == 0 {
+                thread::yield_now();
+            }
+            (0..3).map(|_| push_block(&producer_chain)).collect::<Vec<_>>()
+        });
+        let yielded = (0..3).map(|_| subscription.next().unwrap().unwrap()).collect::<Vec<_>>();
+        assert_eq!(yielded, producer.join().unwrap());
+        assert_eq!(subscription.next_height(), Some(5));
+
+        // Ensure a fork replacing the blocks from height 2 is yielded again from height 2
+        chain.lock().unwrap().truncate(2);
+        let fork = (0..4).map(|_| push_block(&chain)).collect::<Vec<_>>();
+        let yielded = (0..4).map(|_| subscription.next().unwrap().unwrap()).collect::<Vec<_>>();
+        assert_eq!(yielded, fork);
+        assert_eq!(subscription.next_height(), Some(6));
+
+        // Ensure a chain which got shorter is followed from its new tip
+        chain.lock().unwrap().truncate(3);
+        let block = push_block(&chain);
+        assert_eq!(subscription.next().unwrap().unwrap(), block);
+        assert_eq!(subscription.next_height(), Some(4));
+        // Ensure a replaced tip is yielded again, while the blocks below it aren't
+        let blocks = [push_block(&chain), push_block(&chain)];
+        assert_eq!(subscription.by_ref().take(2).map(Result::unwrap).collect::<Vec<_>>(), blocks);
+        chain.lock().unwrap().truncate(5);
+        let block = push_block(&chain);
+        assert_eq!(subscription.next().unwrap().unwrap(), block);
+        assert_eq!(subscription.next_height(), Some(6));
+
+        // Ensure failed requests are yielded as errors without ending the subscription
+        let failing_client = AleoAPIClient::<Testnet3, _>::with_transport(
+            "http://localhost:3030",
+            "testnet3",
+            crate::test_utils::MockTransport::new(),
+        )
+        .unwrap();
+        assert!(failing_client.subscribe_blocks().next().unwrap().is_err());
+
+        // Ensure subscriptions can start from a given height
+        let mut subscription = client.subscribe_blocks_from(Some(0));
+        assert_eq!(subscription.next().unwrap().unwrap(), genesis);
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use serde::de::DeserializeOwned;
+use std::{
+    fmt::{self, Debug},
+    io::Read,
+};
+
+/// HTTP method of a request sent through an [HttpTransport]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HttpMethod {
+    Get,
+    Post,
+}
+
+/// Response received for a request sent through an [HttpTransport]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// The HTTP status code of the response
+    pub status: u16,
+    /// The body of the response
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Create a response with the given status code and body
+    pub fn new(status: u16, body: &str) -> Self {
+        Self { status, body: body.to_string() }
+    }
+
+    /// Determine if the status code of the response indicates success
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Parse the body of the response as JSON
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_str(&self.body)?)
+    }
+}
+
+/// Error returned by an [AleoAPIClient] when a request receives no response, i.e. because the
+/// node can't be reached or the request timed out. Unlike a rejection by the node, the node may
+/// still have received the request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoResponse {
+    /// The URL the request was sent to
+    pub url: String,
+    /// Why no response was received
+    pub reason: String,
+}
+
+impl fmt::Display for NoResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "❌ No response received from {}: {}", self.url, self.reason)
+    }
+}
+
+impl std::error::Error for NoResponse {}
+
+/// HTTP layer used by an [AleoAPIClient] to send requests to an Aleo node.
+///
+/// The [AleoAPIClient] sends requests with [UreqTransport] by default. Implement this trait to
+/// send requests through another HTTP library or to supply canned responses in tests.
+pub trait HttpTransport: Clone + Debug {
+    /// Send a request with an optional JSON body to a URL, failing if it takes longer than the
+    /// timeout given. Responses must be returned whatever their status code, an error should only
+    /// be returned if no response was received.
+    fn request(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        body: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<HttpResponse>;
+}
+
+/// Default [HttpTransport] which sends requests with a pooled [ureq] agent
+///
+/// Response bodies are read in full, unlike [ureq::Response::into_string] which fails on bodies
+/// larger than 10 MB, i.e. a page of large blocks. A limit can be set with
+/// [UreqTransport::with_max_response_size].
+#[derive(Clone, Debug)]
+pub struct UreqTransport {
+    agent: ureq::Agent,
+    max_response_size: Option<usize>,
+}
+
+impl UreqTransport {
+    pub fn new() -> Self {
+        Self { agent: ureq::Agent::new(), max_response_size: None }
+    }
+
+    /// Set the largest response body in bytes which is read, or `None` to read responses of any
+    /// size. Requests receiving a larger response fail.
+    pub fn with_max_response_size(mut self, max_response_size: Option<usize>) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+
+    // Read the body of a response, failing if it is larger than the maximum response size
+    fn read_body(&self, url: &str, response: ureq::Response) -> Result<String> {
+        let mut body = String::new();
+        let mut reader = response.into_reader();
+        let result = match self.max_response_size {
+            // Read one byte past the limit to detect larger responses
+            Some(max_response_size) => reader.take(max_response_size as u64 + 1).read_to_string(&mut body),
+            None => reader.read_to_string(&mut body),
+        };
+        result.map_err(|error| anyhow!("❌ Failed to read the response from {url}: {error}"))?;
+        if let Some(max_response_size) = self.max_response_size {
+            ensure!(
+                body.len() <= max_response_size,
+                "❌ The response from {url} is larger than the limit of {max_response_size} bytes"
+            );
+        }
+        Ok(body)
+    }
+}
+
+impl Default for UreqTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpTransport for UreqTransport {
+    fn request(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        body: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<HttpResponse> {
+        let mut request = match method {
+            HttpMethod::Get => self.agent.get(url),
+            HttpMethod::Post => self.agent.post(url),
+        };
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+        let result = match body {
+            Some(body) => request.set("Content-Type", "application/json").send_string(body),
+            None => request.call(),
+        };
+        match result {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => {
+                let status = response.status();
+                Ok(HttpResponse { status, body: self.read_body(url, response)? })
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransport, HELLO_PROGRAM};
+
+    #[test]
+    fn test_api_client_with_mock_transport() {
+        let transport = MockTransport::new()
+            .with_response(HttpMethod::Get, "http://localhost:3030/testnet3/latest/height", 200, "50")
+            .with_response(
+                HttpMethod::Get,
+                "http://localhost:3030/testnet3/program/hello.aleo",
+                200,
+                &serde_json::to_string(HELLO_PROGRAM).unwrap(),
+            )
+            .with_response(HttpMethod::Get, "http://localhost:3030/testnet3/program/missing.aleo", 404, "Not found");
+        let api_client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+
+        // Ensure responses are parsed
+        assert_eq!(api_client.latest_height().unwrap(), 50);
+        assert_eq!(api_client.get_program("hello.aleo").unwrap(), Program::from_str(HELLO_PROGRAM).unwrap());
+
+        // Ensure unsuccessful responses and requests which receive no response are errors
+        assert!(api_client.get_program("missing.aleo").is_err());
+        assert!(api_client.latest_hash().is_err());
+    }
+
+    #[test]
+    fn test_ureq_transport_reads_large_responses() {
+        use std::{
+            io::Write,
+            net::{TcpListener, TcpStream},
+        };
+
+        // Serve a body larger than the 10 MB ureq reads into a string to every connection
+        let body = "a".repeat(11 * 1024 * 1024);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/testnet3/blocks", listener.local_addr().unwrap());
+        let served_body = body.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().map(Result::<TcpStream, _>::unwrap) {
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request).unwrap();
+                let header = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", served_body.len());
+                let _ = stream.write_all(header.as_bytes()).and_then(|_| stream.write_all(served_body.as_bytes()));
+            }
+        });
+
+        // Ensure the whole body is read without a limit
+        let response = UreqTransport::new().request(HttpMethod::Get, &url, None, None).unwrap();
+        assert_eq!(response.body.len(), body.len());
+
+        // Ensure bodies within the limit are read and larger bodies are rejected
+        let transport = UreqTransport::new().with_max_response_size(Some(body.len()));
+        assert_eq!(transport.request(HttpMethod::Get, &url, None, None).unwrap().body, body);
+        let transport = UreqTransport::new().with_max_response_size(Some(body.len() - 1));
+        let error = transport.request(HttpMethod::Get, &url, None, None).unwrap_err();
+        assert!(error.to_string().contains("larger than the limit"));
+    }
+
+    #[test]
+    fn test_http_response() {
+        let response = HttpResponse::new(200, "[1, 2, 3]");
+        assert!(response.is_success());
+        assert_eq!(response.json::<Vec<u32>>().unwrap(), vec![1, 2, 3]);
+        assert!(response.json::<String>().is_err());
+        assert!(!HttpResponse::new(500, "").is_success());
+    }
+}
@@ -80,7 +80,7 @@
 //! ### Example Usage
 //! ```no_run
 //!   use aleo_rust::{
-//!     AleoAPIClient, Encryptor, ProgramManager, RecordFinder,
+//!     AleoAPIClient, CreditAmount, Encryptor, ProgramManager, RecordFinder,
 //!     snarkvm_types::{Address, PrivateKey, Testnet3, Program}
 //!   };
 //!   use rand::thread_rng;
@@ -104,14 +104,14 @@
 //!   // ------------------
 //!
 //!   let record_finder = RecordFinder::<Testnet3>::new(AleoAPIClient::testnet3());
-//!   // Set the fee for the deployment transaction (in units of microcredits)
-//!   let fee_microcredits = 300000;
-//!   // Find a record to fund the deployment fee (requires an account with a balance)
-//!   let fee_record = record_finder.find_one_record(&private_key, fee_microcredits).unwrap();
+//!   // Set the fee for the execution transaction (in units of credits)
+//!   let fee = CreditAmount::from_str("0.3").unwrap();
+//!   // Find a record to fund the execution fee (requires an account with a balance)
+//!   let fee_record = record_finder.find_one_record(&private_key, fee.microcredits()).unwrap();
 //!
 //!   // Execute the function `main` of the hello.aleo program with the arguments 5u32 and 3u32.
-//!   // Specify 0 for the fee and provide a password to decrypt the private key stored in the program manager
-//!   program_manager.execute_program("hello.aleo", "main", ["5u32", "3u32"].into_iter(), 0, fee_record, Some("password")).unwrap();
+//!   // Provide a password to decrypt the private key stored in the program manager
+//!   program_manager.execute_program("hello.aleo", "main", ["5u32", "3u32"].into_iter(), fee, fee_record, Some("password")).unwrap();
 //!
 //!   // ------------------
 //!   // DEPLOY PROGRAM STEPS
@@ -131,12 +131,12 @@
 //!   program_manager.add_program(&program).unwrap();
 //!   // Create a record finder to find records to fund the deployment fee
 //!   let record_finder = RecordFinder::<Testnet3>::new(AleoAPIClient::testnet3());
-//!   // Set the fee for the deployment transaction (in units of microcredits)
-//!   let fee_microcredits = 300000;
+//!   // Set the fee for the deployment transaction (in units of credits)
+//!   let fee = CreditAmount::from_str("0.3").unwrap();
 //!   // Find a record to fund the deployment fee (requires an account with a balance)
-//!   let fee_record = record_finder.find_one_record(&private_key, fee_microcredits).unwrap();
+//!   let fee_record = record_finder.find_one_record(&private_key, fee.microcredits()).unwrap();
 //!   // Deploy the program to the network
-//!   program_manager.deploy_program(program_name, fee_microcredits, fee_record, Some("password")).unwrap();
+//!   program_manager.deploy_program(program_name, fee, fee_record, Some("password")).unwrap();
 //!
 //!   // Wait several minutes.. then check the program exists on the network
 //!   let api_client = AleoAPIClient::<Testnet3>::testnet3();
@@ -151,11 +151,11 @@
 //!   // Create a recipient (in practice, the recipient would send their address to the sender)
 //!   let recipient_key = PrivateKey::<Testnet3>::new(&mut rng).unwrap();
 //!   let recipient_address = Address::try_from(recipient_key).unwrap();
-//!   // Create amount and fee (both in units of microcredits)
+//!   // Create amount (in units of microcredits) and fee
 //!   let amount = 30000;
-//!   let fee = 100;
+//!   let fee = CreditAmount::from_microcredits(100);
 //!   // Find records to fund the transfer
-//!   let (amount_record, fee_record) = record_finder.find_amount_and_fee_records(amount, fee, &private_key).unwrap();
+//!   let (amount_record, fee_record) = record_finder.find_amount_and_fee_records(amount, fee.microcredits(), &private_key).unwrap();
 //!   // Create a transfer
 //!   program_manager.transfer(amount, fee, recipient_address, Some("password"), amount_record, fee_record).unwrap();
 //!
@@ -166,19 +166,23 @@
 
 pub mod account;
 #[doc(inline)]
-pub use account::Encryptor;
+pub use account::{generate_account, Encryptor};
+
+pub mod amount;
+#[doc(inline)]
+pub use amount::CreditAmount;
 
 #[cfg(feature = "full")]
 pub mod api;
 #[cfg(feature = "full")]
 #[doc(inline)]
-pub use api::AleoAPIClient;
+pub use api::{AleoAPIClient, HttpMethod, HttpResponse, HttpTransport, NetworkConfig, NoResponse, UreqTransport};
 
 #[cfg(feature = "full")]
 pub mod program;
 #[cfg(feature = "full")]
 #[doc(inline)]
-pub use program::{OnChainProgramState, ProgramManager, RecordFinder};
+pub use program::{EncryptedAccountBackup, NoSigningKey, OnChainProgramState, ProgramManager, RecordFinder};
 
 #[cfg(test)]
 #[cfg(feature = "full")]
@@ -190,7 +194,16 @@ pub use test_utils::*;
 pub mod snarkvm_types {
     //! Re-export of crucial types from the snarkVM crate
     #[cfg(feature = "full")]
-    pub use snarkvm::synthesizer::{Block, ConsensusMemory, ConsensusStore, Program, Query, Transaction, VM};
+    pub use snarkvm::synthesizer::{
+        Block,
+        ConsensusMemory,
+        ConsensusStore,
+        Program,
+        Query,
+        Transaction,
+        Transition,
+        VM,
+    };
     pub use snarkvm_console::{
         account::{Address, PrivateKey, Signature, ViewKey},
         network::Testnet3,
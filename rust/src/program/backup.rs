@@ -0,0 +1,115 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::fmt;
+
+/// A password encrypted backup of an account, created with [ProgramManager::export_account].
+///
+/// The view key and address of an account are derived from its private key, so the backup only
+/// holds the ciphertext of the private key and the full keyset is recovered from it by
+/// [ProgramManager::import_account]. A backup is displayed as its ciphertext string, so it can be
+/// written to a file and parsed back.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EncryptedAccountBackup<N: Network> {
+    ciphertext: Ciphertext<N>,
+}
+
+impl<N: Network> EncryptedAccountBackup<N> {
+    /// Get the ciphertext of the account's private key. It can be used as the private key
+    /// ciphertext of a program manager, with the password of the backup.
+    pub fn ciphertext(&self) -> &Ciphertext<N> {
+        &self.ciphertext
+    }
+}
+
+impl<N: Network> FromStr for EncryptedAccountBackup<N> {
+    type Err = Error;
+
+    fn from_str(backup: &str) -> Result<Self> {
+        let ciphertext = Ciphertext::from_str(backup.trim())
+            .map_err(|error| anyhow!("❌ Failed to parse the account backup: {error}"))?;
+        Ok(Self { ciphertext })
+    }
+}
+
+impl<N: Network> fmt::Display for EncryptedAccountBackup<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ciphertext)
+    }
+}
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Export the program manager's account as a backup encrypted with a password.
+    ///
+    /// If the program manager's private key is stored as ciphertext, the same password is used to
+    /// decrypt it.
+    pub fn export_account(&self, password: &str) -> Result<EncryptedAccountBackup<N>> {
+        ensure!(!password.is_empty(), "❌ A password is required to encrypt the account backup");
+        let private_key = self.get_private_key(Some(password))?;
+        let ciphertext = Encryptor::encrypt_private_key_with_secret(&private_key, password)?;
+        Ok(EncryptedAccountBackup { ciphertext })
+    }
+
+    /// Decrypt an account backup created with [ProgramManager::export_account], returning the
+    /// private key, view key and address of the account
+    pub fn import_account(
+        backup: &EncryptedAccountBackup<N>,
+        password: &str,
+    ) -> Result<(PrivateKey<N>, ViewKey<N>, Address<N>)> {
+        let private_key = Encryptor::decrypt_private_key_with_secret(&backup.ciphertext, password)
+            .map_err(|_| anyhow!("❌ Failed to decrypt the account backup, the password may be incorrect"))?;
+        let view_key = ViewKey::try_from(&private_key)?;
+        let address = Address::try_from(&view_key)?;
+        Ok((private_key, view_key, address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{RECIPIENT_ADDRESS, RECIPIENT_PRIVATE_KEY};
+
+    #[test]
+    fn test_account_backup() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+
+        // Ensure the keyset survives a round trip through the backup's string
+        let backup = program_manager.export_account("password").unwrap();
+        assert!(!backup.to_string().contains(RECIPIENT_PRIVATE_KEY));
+        let backup = EncryptedAccountBackup::<Testnet3>::from_str(&backup.to_string()).unwrap();
+        let (imported_private_key, view_key, address) =
+            ProgramManager::<Testnet3>::import_account(&backup, "password").unwrap();
+        assert_eq!(imported_private_key, private_key);
+        assert_eq!(view_key, ViewKey::try_from(&private_key).unwrap());
+        assert_eq!(address.to_string(), RECIPIENT_ADDRESS);
+
+        // Ensure wrong passwords and empty passwords are rejected
+        let error = ProgramManager::<Testnet3>::import_account(&backup, "wrong_password").unwrap_err();
+        assert!(error.to_string().contains("password may be incorrect"));
+        assert!(program_manager.export_account("").is_err());
+        assert!(EncryptedAccountBackup::<Testnet3>::from_str("backup").is_err());
+
+        // Ensure an account with an encrypted private key is exported with the same password
+        let program_manager =
+            ProgramManager::<Testnet3>::new(None, Some(backup.ciphertext().clone()), None, None).unwrap();
+        let backup = program_manager.export_account("password").unwrap();
+        assert_eq!(ProgramManager::<Testnet3>::import_account(&backup, "password").unwrap().0, private_key);
+        assert!(program_manager.export_account("wrong_password").is_err());
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm_console::account::GraphKey;
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Compute the on-chain commitment of a `credits.aleo/credits` record.
+    ///
+    /// The commitment is deterministic, so wallets can match decrypted records against the
+    /// commitments found in the ledger without broadcasting anything. Use
+    /// [ProgramManager::program_record_commitment] for records of other programs.
+    pub fn record_commitment(&self, record: &Record<N, Plaintext<N>>) -> Result<Field<N>> {
+        self.program_record_commitment(record, "credits.aleo", "credits")
+    }
+
+    /// Compute the on-chain commitment of a record with the given record name created by the
+    /// given program
+    pub fn program_record_commitment(
+        &self,
+        record: &Record<N, Plaintext<N>>,
+        program_id: impl TryInto<ProgramID<N>>,
+        record_name: impl TryInto<Identifier<N>>,
+    ) -> Result<Field<N>> {
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        let record_name = record_name.try_into().map_err(|_| anyhow!("Invalid record name"))?;
+        record.to_commitment(&program_id, &record_name)
+    }
+
+    /// Compute the tags of the records with the given commitments owned by a view key.
+    ///
+    /// A record's tag is revealed in the transition which spends it, so a wallet holding only a
+    /// view key can detect when its records are spent by matching these tags against the inputs
+    /// of transitions, see [AleoAPIClient::find_spent_tags]. Unlike serial numbers, tags can be
+    /// computed without the private key.
+    pub fn compute_record_tags(&self, view_key: &ViewKey<N>, commitments: &[Field<N>]) -> Result<Vec<Field<N>>> {
+        let sk_tag = GraphKey::try_from(view_key)?.sk_tag();
+        commitments.iter().map(|commitment| Record::<N, Plaintext<N>>::tag(sk_tag, *commitment)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{RECIPIENT_PRIVATE_KEY, RECORD_5_MICROCREDITS};
+
+    // Commitment of RECORD_5_MICROCREDITS as a credits.aleo/credits record
+    const RECORD_5_MICROCREDITS_COMMITMENT: &str =
+        "8409849612391280654932846588080575112011316105663639643380772733208113733736field";
+
+    #[test]
+    fn test_record_commitment() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let record = Record::<Testnet3, Plaintext<Testnet3>>::from_str(RECORD_5_MICROCREDITS).unwrap();
+
+        // Ensure the commitment matches the known commitment of the record
+        let commitment = program_manager.record_commitment(&record).unwrap();
+        assert_eq!(commitment, Field::from_str(RECORD_5_MICROCREDITS_COMMITMENT).unwrap());
+
+        // Ensure the commitment is deterministic
+        assert_eq!(program_manager.record_commitment(&record).unwrap(), commitment);
+        assert_eq!(program_manager.program_record_commitment(&record, "credits.aleo", "credits").unwrap(), commitment);
+
+        // Ensure the commitment is bound to the program and record name
+        assert_ne!(program_manager.program_record_commitment(&record, "hello.aleo", "credits").unwrap(), commitment);
+        assert_ne!(program_manager.program_record_commitment(&record, "credits.aleo", "token").unwrap(), commitment);
+        assert!(program_manager.program_record_commitment(&record, "credits", "credits").is_err());
+    }
+
+    #[test]
+    fn test_compute_record_tags() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let commitments =
+            [Field::from_str(RECORD_5_MICROCREDITS_COMMITMENT).unwrap(), Field::from_str("1field").unwrap()];
+
+        // Ensure a distinct tag is computed for each commitment
+        let tags = program_manager.compute_record_tags(&view_key, &commitments).unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_ne!(tags[0], tags[1]);
+        assert_eq!(program_manager.compute_record_tags(&view_key, &commitments[..1]).unwrap(), tags[..1]);
+
+        // Ensure the tags are bound to the view key
+        let other_view_key = ViewKey::try_from(PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap()).unwrap();
+        assert_ne!(program_manager.compute_record_tags(&other_view_key, &commitments).unwrap(), tags);
+        assert!(program_manager.compute_record_tags(&view_key, &[]).unwrap().is_empty());
+    }
+}
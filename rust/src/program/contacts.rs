@@ -0,0 +1,118 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// The recipient of a transfer, given either as an address or as the label of a contact added
+/// with [ProgramManager::add_contact]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Recipient<N: Network> {
+    /// An address
+    Address(Address<N>),
+    /// The label of a contact, or an address written as a string
+    Contact(String),
+}
+
+impl<N: Network> From<Address<N>> for Recipient<N> {
+    fn from(address: Address<N>) -> Self {
+        Self::Address(address)
+    }
+}
+
+impl<N: Network> From<&str> for Recipient<N> {
+    fn from(label: &str) -> Self {
+        Self::Contact(label.to_string())
+    }
+}
+
+impl<N: Network> From<String> for Recipient<N> {
+    fn from(label: String) -> Self {
+        Self::Contact(label)
+    }
+}
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Add a contact to the program manager's address book, so transfers can be sent to its
+    /// label instead of its address. Returns the address previously known by the label, if any.
+    pub fn add_contact(&mut self, label: &str, address: Address<N>) -> Result<Option<Address<N>>> {
+        let label = label.trim();
+        ensure!(!label.is_empty(), "❌ Contact labels can't be empty");
+        ensure!(Address::<N>::from_str(label).is_err(), "❌ Contact labels can't be addresses");
+        Ok(self.contacts.insert(label.to_string(), address))
+    }
+
+    /// Remove a contact from the program manager's address book, returning its address
+    pub fn remove_contact(&mut self, label: &str) -> Option<Address<N>> {
+        self.contacts.shift_remove(label.trim())
+    }
+
+    /// Get the address of a contact in the program manager's address book
+    pub fn get_contact(&self, label: &str) -> Option<Address<N>> {
+        self.contacts.get(label.trim()).copied()
+    }
+
+    /// Get the contacts in the program manager's address book, in the order they were added
+    pub fn contacts(&self) -> &IndexMap<String, Address<N>> {
+        &self.contacts
+    }
+
+    /// Find the address of a recipient, looking up contact labels in the address book
+    pub fn resolve_recipient(&self, recipient: impl Into<Recipient<N>>) -> Result<Address<N>> {
+        match recipient.into() {
+            Recipient::Address(address) => Ok(address),
+            Recipient::Contact(label) => self
+                .get_contact(&label)
+                .or_else(|| Address::from_str(label.trim()).ok())
+                .ok_or_else(|| anyhow!("❌ '{label}' is neither a known contact nor a valid address")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{RECIPIENT_ADDRESS, RECIPIENT_PRIVATE_KEY};
+
+    #[test]
+    fn test_contacts() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let address = Address::<Testnet3>::from_str(RECIPIENT_ADDRESS).unwrap();
+        let other_address = Address::try_from(PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap()).unwrap();
+
+        // Ensure contacts are added and replaced by label
+        assert_eq!(program_manager.add_contact("alice", address).unwrap(), None);
+        assert_eq!(program_manager.add_contact(" alice ", other_address).unwrap(), Some(address));
+        assert_eq!(program_manager.get_contact("alice"), Some(other_address));
+        assert_eq!(program_manager.contacts().len(), 1);
+
+        // Ensure recipients resolve from addresses, contact labels and address strings
+        assert_eq!(program_manager.resolve_recipient(address).unwrap(), address);
+        assert_eq!(program_manager.resolve_recipient("alice").unwrap(), other_address);
+        assert_eq!(program_manager.resolve_recipient(RECIPIENT_ADDRESS).unwrap(), address);
+        let error = program_manager.resolve_recipient("bob").unwrap_err();
+        assert!(error.to_string().contains("'bob' is neither a known contact nor a valid address"));
+
+        // Ensure empty labels and labels which are addresses are rejected
+        assert!(program_manager.add_contact(" ", address).is_err());
+        assert!(program_manager.add_contact(RECIPIENT_ADDRESS, other_address).is_err());
+
+        // Ensure removed contacts can no longer be resolved
+        assert_eq!(program_manager.remove_contact("alice"), Some(other_address));
+        assert!(program_manager.resolve_recipient("alice").is_err());
+        assert!(program_manager.contacts().is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::synthesizer::{Input, Output};
+use snarkvm_console::{prelude::ToBits, program::ValueType, types::U16};
+
+/// A transition input or output decoded with [ProgramManager::decode_transition]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedEntry<N: Network> {
+    /// The register of the function input or output, i.e. `r0`
+    pub name: String,
+    /// The type and visibility of the input or output
+    pub value_type: ValueType<N>,
+    /// The ID of the input or output, which is the serial number of an input record and the
+    /// commitment of an output record
+    pub id: Field<N>,
+    /// The value of the input or output, or `None` if the program manager's account can't read
+    /// it. Input records are only identified by their serial number, so their value is always
+    /// `None`.
+    pub value: Option<Value<N>>,
+}
+
+/// A transition whose inputs and outputs are labelled and decrypted, found with
+/// [ProgramManager::decode_transition]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedTransition<N: Network> {
+    /// The ID of the transition
+    pub id: N::TransitionID,
+    /// The program executed by the transition
+    pub program_id: ProgramID<N>,
+    /// The function executed by the transition
+    pub function_name: Identifier<N>,
+    /// Whether the program manager's account made the call executed by the transition
+    pub is_caller: bool,
+    /// The inputs of the transition, in the order the function declares them
+    pub inputs: Vec<DecodedEntry<N>>,
+    /// The outputs of the transition, in the order the function declares them
+    pub outputs: Vec<DecodedEntry<N>>,
+}
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Decode a transition into a display ready description of what it did, i.e. for an
+    /// activity feed.
+    ///
+    /// The function executed by the transition is found in the program manager, on disk or on
+    /// the Aleo network and its inputs and outputs are labelled with the registers and types it
+    /// declares. Constant and public values are readable by anyone. Private values are decrypted
+    /// if the program manager's account made the call and output records are decrypted if the
+    /// account owns them, otherwise their value is left empty.
+    pub fn decode_transition(
+        &self,
+        transition: &Transition<N>,
+        password: Option<&str>,
+    ) -> Result<DecodedTransition<N>> {
+        let program_id = transition.program_id();
+        let function_name = transition.function_name();
+        let function = self.load_function(program_id, function_name)?;
+        ensure!(
+            function.inputs().len() == transition.inputs().len()
+                && function.outputs().len() == transition.outputs().len(),
+            "❌ Transition {} does not match the inputs and outputs of function {program_id}/{function_name}",
+            transition.id()
+        );
+
+        // The caller's view key recovers the transition view key, which encrypts the private
+        // inputs and outputs of the transition
+        let view_key = ViewKey::try_from(self.get_private_key(password)?)?;
+        let caller_tvk = Self::caller_transition_view_key(transition, &view_key)?;
+        let function_id = N::hash_bhp1024(
+            &(U16::<N>::new(N::ID), program_id.name(), program_id.network(), function_name).to_bits_le(),
+        )?;
+        let decrypt = |ciphertext: &Option<Ciphertext<N>>, index: usize| -> Result<Option<Value<N>>> {
+            match (ciphertext, caller_tvk) {
+                (Some(ciphertext), Some(tvk)) => {
+                    let index = Field::from_u16(u16::try_from(index)?);
+                    let plaintext = ciphertext.decrypt_symmetric(N::hash_psd4(&[function_id, tvk, index])?)?;
+                    Ok(Some(Value::Plaintext(plaintext)))
+                }
+                _ => Ok(None),
+            }
+        };
+
+        let inputs = function
+            .inputs()
+            .iter()
+            .zip(transition.inputs())
+            .enumerate()
+            .map(|(index, (function_input, input))| {
+                let value = match input {
+                    Input::Constant(_, plaintext) | Input::Public(_, plaintext) => {
+                        plaintext.clone().map(Value::Plaintext)
+                    }
+                    Input::Private(_, ciphertext) => decrypt(ciphertext, index)?,
+                    Input::Record(..) | Input::ExternalRecord(..) => None,
+                };
+                Ok(DecodedEntry {
+                    name: function_input.register().to_string(),
+                    value_type: *function_input.value_type(),
+                    id: *input.id(),
+                    value,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let num_inputs = inputs.len();
+        let outputs = function
+            .outputs()
+            .iter()
+            .zip(transition.outputs())
+            .enumerate()
+            .map(|(index, (function_output, output))| {
+                let value = match output {
+                    Output::Constant(_, plaintext) | Output::Public(_, plaintext) => {
+                        plaintext.clone().map(Value::Plaintext)
+                    }
+                    Output::Private(_, ciphertext) => decrypt(ciphertext, num_inputs + index)?,
+                    Output::Record(_, _, Some(record)) if record.is_owner(&view_key) => {
+                        Some(Value::Record(record.decrypt(&view_key)?))
+                    }
+                    Output::Record(..) | Output::ExternalRecord(..) => None,
+                };
+                Ok(DecodedEntry {
+                    name: function_output.operand().to_string(),
+                    value_type: *function_output.value_type(),
+                    id: *output.id(),
+                    value,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(DecodedTransition {
+            id: *transition.id(),
+            program_id: *program_id,
+            function_name: *function_name,
+            is_caller: caller_tvk.is_some(),
+            inputs,
+            outputs,
+        })
+    }
+
+    // Recover the transition view key of a transition if the view key belongs to the account
+    // which made the call executed by the transition
+    pub(crate) fn caller_transition_view_key(
+        transition: &Transition<N>,
+        view_key: &ViewKey<N>,
+    ) -> Result<Option<Field<N>>> {
+        let tvk = (*transition.tpk() * **view_key).to_x_coordinate();
+        Ok((N::hash_psd2(&[tvk])? == *transition.tcm()).then_some(tvk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{BEACON_PRIVATE_KEY, EXECUTE_TRANSACTION, HELLO_PROGRAM, RECIPIENT_PRIVATE_KEY};
+
+    #[test]
+    fn test_decode_transition() {
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+        let program_manager = |private_key: &str| {
+            let private_key = PrivateKey::<Testnet3>::from_str(private_key).unwrap();
+            let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+            program_manager.add_program(&Program::from_str(HELLO_PROGRAM).unwrap()).unwrap();
+            program_manager.add_program(&Program::credits().unwrap()).unwrap();
+            program_manager
+        };
+        let value = |value: &str| Some(Value::<Testnet3>::from_str(value).unwrap());
+        let (main, fee) = {
+            let mut transitions = transaction.transitions();
+            (transitions.next().unwrap(), transitions.next().unwrap())
+        };
+
+        // Ensure the caller can read every value of the transition except its input records
+        let caller = program_manager(RECIPIENT_PRIVATE_KEY);
+        let decoded = caller.decode_transition(main, None).unwrap();
+        assert_eq!(decoded.id, *main.id());
+        assert_eq!(decoded.program_id.to_string(), "hello.aleo");
+        assert_eq!(decoded.function_name.to_string(), "main");
+        assert!(decoded.is_caller);
+        let names = decoded.inputs.iter().chain(&decoded.outputs).map(|entry| entry.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["r0", "r1", "r2"]);
+        assert_eq!(decoded.inputs[1].value_type.to_string(), "u32.private");
+        assert_eq!(decoded.inputs[0].value, value("5u32"));
+        assert_eq!(decoded.inputs[1].value, value("5u32"));
+        assert_eq!(decoded.outputs[0].value, value("10u32"));
+
+        let decoded = caller.decode_transition(fee, None).unwrap();
+        assert_eq!(decoded.inputs[0].value_type.to_string(), "credits.record");
+        assert_eq!(decoded.inputs[0].id, *fee.serial_numbers().next().unwrap());
+        assert_eq!(decoded.inputs[0].value, None);
+        assert_eq!(decoded.inputs[1].value, value("2000000u64"));
+        assert!(matches!(decoded.outputs[0].value, Some(Value::Record(_))));
+
+        // Ensure another account can only read the public values
+        let observer = program_manager(BEACON_PRIVATE_KEY);
+        let decoded = observer.decode_transition(main, None).unwrap();
+        assert!(!decoded.is_caller);
+        assert_eq!(decoded.inputs[0].value, value("5u32"));
+        assert_eq!(decoded.inputs[1].value, None);
+        assert_eq!(decoded.outputs[0].value, None);
+        assert_eq!(observer.decode_transition(fee, None).unwrap().outputs[0].value, None);
+
+        // Ensure transitions of unknown programs can't be decoded
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        assert!(program_manager.decode_transition(main, None).is_err());
+    }
+}
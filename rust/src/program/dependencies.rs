@@ -0,0 +1,148 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// The import graph of a program, found with [ProgramManager::dependency_graph]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DependencyGraph<N: Network> {
+    /// The programs in the graph, each listed after all of the programs it imports. Deploying the
+    /// programs in this order ensures every import is deployed before the programs using it.
+    pub nodes: Vec<ProgramID<N>>,
+    /// The imports in the graph as (importing program, imported program) pairs
+    pub edges: Vec<(ProgramID<N>, ProgramID<N>)>,
+}
+
+impl<N: Network> DependencyGraph<N> {
+    /// Get the programs directly imported by a program in the graph
+    pub fn imports<'a>(&'a self, program_id: &'a ProgramID<N>) -> impl Iterator<Item = &'a ProgramID<N>> {
+        self.edges.iter().filter(move |(importer, _)| importer == program_id).map(|(_, imported)| imported)
+    }
+}
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Resolve the imports of a program recursively and return its full import graph.
+    ///
+    /// Programs are looked up in the program manager first, then on disk and on the Aleo Network.
+    /// Programs importing each other in a cycle can never be deployed, so an error naming the
+    /// programs in the cycle is returned if one is found.
+    pub fn dependency_graph(&self, program_id: impl TryInto<ProgramID<N>>) -> Result<DependencyGraph<N>> {
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        let mut graph = DependencyGraph { nodes: vec![], edges: vec![] };
+        self.add_to_dependency_graph(program_id, &mut vec![], &mut graph)?;
+        Ok(graph)
+    }
+
+    // Add a program and its imports to the graph with a depth first search, keeping the path of
+    // programs being resolved in order to detect cycles
+    fn add_to_dependency_graph(
+        &self,
+        program_id: ProgramID<N>,
+        path: &mut Vec<ProgramID<N>>,
+        graph: &mut DependencyGraph<N>,
+    ) -> Result<()> {
+        if graph.nodes.contains(&program_id) {
+            return Ok(());
+        }
+        if let Some(start) = path.iter().position(|id| id == &program_id) {
+            let cycle = path[start..].iter().chain([&program_id]).map(|id| id.to_string()).collect::<Vec<_>>();
+            bail!("❌ Programs import each other in a cycle: {}", cycle.join(" -> "));
+        }
+
+        // credits.aleo is built into every network and has no imports
+        let imports = if program_id.to_string() == "credits.aleo" {
+            vec![]
+        } else {
+            let program = if self.contains_program(program_id)? {
+                self.get_program(program_id)
+            } else {
+                self.find_program(&program_id)
+            }
+            .map_err(|_| anyhow!("❌ Program {program_id:?} could not be found locally or on the Aleo Network"))?;
+            program.imports().keys().copied().collect::<Vec<_>>()
+        };
+
+        path.push(program_id);
+        for import in imports {
+            graph.edges.push((program_id, import));
+            self.add_to_dependency_graph(import, path, graph)?;
+        }
+        path.pop();
+        graph.nodes.push(program_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RECIPIENT_PRIVATE_KEY;
+
+    // Create a program with the given imports
+    fn program_with_imports(program_id: &str, imports: &[&str]) -> Program<Testnet3> {
+        let imports = imports.iter().map(|import| format!("import {import};\n")).collect::<String>();
+        Program::from_str(&format!(
+            "{imports}program {program_id};
+
+function main:
+    input r0 as u32.public;
+    output r0 as u32.private;
+"
+        ))
+        .unwrap()
+    }
+
+    fn id(program_id: &str) -> ProgramID<Testnet3> {
+        ProgramID::from_str(program_id).unwrap()
+    }
+
+    #[test]
+    fn test_dependency_graph() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        program_manager.add_program(&program_with_imports("app.aleo", &["token.aleo", "credits.aleo"])).unwrap();
+        program_manager.add_program(&program_with_imports("token.aleo", &["math.aleo", "credits.aleo"])).unwrap();
+        program_manager.add_program(&program_with_imports("math.aleo", &[])).unwrap();
+
+        // Ensure every import is listed before the programs importing it
+        let graph = program_manager.dependency_graph("app.aleo").unwrap();
+        assert_eq!(graph.nodes, vec![id("math.aleo"), id("credits.aleo"), id("token.aleo"), id("app.aleo")]);
+        assert_eq!(graph.edges.len(), 4);
+        assert_eq!(graph.imports(&id("app.aleo")).collect::<Vec<_>>(), vec![&id("token.aleo"), &id("credits.aleo")]);
+        assert_eq!(graph.imports(&id("math.aleo")).count(), 0);
+
+        // Ensure a program without imports is the only node of its graph
+        let graph = program_manager.dependency_graph("math.aleo").unwrap();
+        assert_eq!(graph, DependencyGraph { nodes: vec![id("math.aleo")], edges: vec![] });
+
+        // Ensure missing imports are reported
+        program_manager.add_program(&program_with_imports("broken.aleo", &["missing.aleo"])).unwrap();
+        let error = program_manager.dependency_graph("broken.aleo").unwrap_err();
+        assert!(error.to_string().contains("missing.aleo"));
+    }
+
+    #[test]
+    fn test_dependency_graph_detects_cycles() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        program_manager.add_program(&program_with_imports("app.aleo", &["first.aleo"])).unwrap();
+        program_manager.add_program(&program_with_imports("first.aleo", &["second.aleo"])).unwrap();
+        program_manager.add_program(&program_with_imports("second.aleo", &["first.aleo"])).unwrap();
+
+        let error = program_manager.dependency_graph("app.aleo").unwrap_err();
+        assert!(error.to_string().contains("first.aleo -> second.aleo -> first.aleo"));
+    }
+}
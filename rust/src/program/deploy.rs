@@ -16,15 +16,77 @@
 
 use super::*;
 
-impl<N: Network> ProgramManager<N> {
-    /// Deploy a program to the network
+use rand::{CryptoRng, Rng};
+use snarkvm::synthesizer::{Deployment, Fee};
+use snarkvm_console::program::ProgramOwner;
+
+/// Result of a program deployment broadcast to the network
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeploymentResult<N: Network> {
+    /// The ID of the deploy transaction
+    pub transaction_id: N::TransactionID,
+    /// The ID of the program deployed
+    pub program_id: ProgramID<N>,
+    /// The fee paid for the deployment in microcredits
+    pub fee_paid: u64,
+    /// The height of the block the deployment was confirmed in, only known once the deployment
+    /// has been waited for with [ProgramManager::wait_for_deployment]
+    pub block_height: Option<u32>,
+}
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Deploy a program to the network, returning the ID of the deploy transaction along with the
+    /// program ID and fee paid.
+    ///
+    /// The program and its imports are only loaded into a VM built for the deploy transaction, so
+    /// the program manager is left unchanged if the deployment fails at any point
     pub fn deploy_program(
         &mut self,
         program_id: impl TryInto<ProgramID<N>>,
-        fee: u64,
+        fee: CreditAmount,
         fee_record: Record<N, Plaintext<N>>,
         password: Option<&str>,
-    ) -> Result<String> {
+    ) -> Result<DeploymentResult<N>> {
+        let fee = fee.microcredits();
+        let _reservation = self.reserve_records(&[&fee_record])?;
+
+        // Check the deployment can be made and load the program and its imports
+        let (program, imports) = self.prepare_deployment(program_id, fee, &fee_record)?;
+
+        // Try to get the private key
+        let private_key = self.get_private_key(password)?;
+
+        // Attempt to construct the transaction
+        println!("Building transaction..");
+        let query = self.api_client.as_ref().unwrap().base_url();
+        let transaction = Self::build_deploy_transaction(
+            &program,
+            &imports,
+            &private_key,
+            fee,
+            fee_record,
+            query.to_string(),
+            &|phase| self.report_progress(phase),
+        )?;
+
+        self.broadcast_deployment(program.id(), transaction, fee)
+    }
+
+    /// Wait for a deployment to be confirmed on chain, returning the deployment result with the
+    /// height of the block it was confirmed in
+    pub fn wait_for_deployment(&self, deployment: DeploymentResult<N>) -> Result<DeploymentResult<N>> {
+        let confirmed = self.wait_for_confirmation(deployment.transaction_id, 0)?;
+        Ok(DeploymentResult { block_height: Some(confirmed.block_height), ..deployment })
+    }
+
+    // Check a program can be deployed with the given fee, returning the program along with the
+    // imports which must be loaded to deploy it
+    pub(crate) fn prepare_deployment(
+        &self,
+        program_id: impl TryInto<ProgramID<N>>,
+        fee: u64,
+        fee_record: &Record<N, Plaintext<N>>,
+    ) -> Result<(Program<N>, Vec<Program<N>>)> {
         // Ensure a network client is configured, otherwise deployment is not possible
         ensure!(
             self.api_client.is_some(),
@@ -33,11 +95,7 @@ impl<N: Network> ProgramManager<N> {
 
         // Ensure a fee is specified and the record has enough balance to pay for it
         ensure!(fee > 0, "❌ Fee must be greater than zero in order to deploy a program");
-        let record_amount = fee_record.microcredits()?;
-        ensure!(
-            record_amount >= fee,
-            "❌ The record supplied has balance of {record_amount:?} microcredits which is insufficient to pay the specified fee of {fee:?} microcredits"
-        );
+        InsufficientFunds::check(&[fee_record], fee)?;
 
         // Check program has a valid name
         let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
@@ -97,15 +155,16 @@ impl<N: Network> ProgramManager<N> {
             Ok::<_, Error>(())
         })?;
 
-        // Try to get the private key
-        let private_key = self.get_private_key(password)?;
-
-        // Attempt to construct the transaction
-        println!("Building transaction..");
-        let query = self.api_client.as_ref().unwrap().base_url();
-        let transaction =
-            Self::create_deploy_transaction(&program, &imports, &private_key, fee, fee_record, query.to_string())?;
+        Ok((program, imports))
+    }
 
+    // Broadcast a deploy transaction, ensuring the fee is sufficient to pay for it
+    pub(crate) fn broadcast_deployment(
+        &self,
+        program_id: &ProgramID<N>,
+        transaction: Transaction<N>,
+        fee: u64,
+    ) -> Result<DeploymentResult<N>> {
         println!(
             "Attempting to broadcast a deploy transaction for program {:?} to node {:?}",
             program_id,
@@ -113,8 +172,9 @@ impl<N: Network> ProgramManager<N> {
         );
 
         // Ensure the fee is sufficient to pay for the transaction
-        let required_fee = transaction.to_bytes_le()?.len();
-        let result = if usize::try_from(fee)? >= required_fee {
+        let transaction_id = transaction.id();
+        let required_fee = self.minimum_fee_for(&transaction)?;
+        let result = if fee >= required_fee {
             self.broadcast_transaction(transaction)
         } else {
             bail!(
@@ -131,7 +191,8 @@ impl<N: Network> ProgramManager<N> {
             println!("❌ Deployment transaction for {program_id:?} failed to broadcast");
         };
 
-        result
+        result?;
+        Ok(DeploymentResult { transaction_id, program_id: *program_id, fee_paid: fee, block_height: None })
     }
 
     /// Create a deploy transaction for a program without instantiating the program manager
@@ -142,6 +203,19 @@ impl<N: Network> ProgramManager<N> {
         fee: u64,
         fee_record: Record<N, Plaintext<N>>,
         query: String,
+    ) -> Result<Transaction<N>> {
+        Self::build_deploy_transaction(program, imports, private_key, fee, fee_record, query, &|_| ())
+    }
+
+    // Create a deploy transaction, reporting the phases of the deployment
+    fn build_deploy_transaction(
+        program: &Program<N>,
+        imports: &[Program<N>],
+        private_key: &PrivateKey<N>,
+        fee: u64,
+        fee_record: Record<N, Plaintext<N>>,
+        query: String,
+        progress: &dyn Fn(TransactionPhase),
     ) -> Result<Transaction<N>> {
         // Initialize an RNG.
         let rng = &mut rand::thread_rng();
@@ -157,7 +231,31 @@ impl<N: Network> ProgramManager<N> {
             Ok::<_, Error>(())
         })?;
 
-        Transaction::<N>::deploy(&vm, private_key, program, (fee_record, fee), Some(query), rng)
+        // Synthesize the deployment and pay for it
+        progress(TransactionPhase::Synthesizing);
+        let deployment = vm.deploy(program, rng)?;
+        ensure!(!deployment.program().functions().is_empty(), "❌ Attempted to deploy a program without functions");
+        progress(TransactionPhase::Proving);
+        let (_, fee, _) = vm.execute_fee(private_key, fee_record, fee, Some(query), rng)?;
+        Self::sign_deployment(private_key, deployment, fee, rng)
+    }
+
+    // Build a deploy transaction whose program owner is signed by the given private key. snarkVM
+    // derives the transaction ID the owner signs from the deployment and fee only, so it is
+    // computed by snarkVM on the transaction with a placeholder owner before it is signed.
+    pub(crate) fn sign_deployment<R: Rng + CryptoRng>(
+        private_key: &PrivateKey<N>,
+        deployment: Deployment<N>,
+        fee: Fee<N>,
+        rng: &mut R,
+    ) -> Result<Transaction<N>> {
+        let placeholder_id = Field::<N>::from_u64(0).into();
+        let placeholder_owner = ProgramOwner::new(private_key, placeholder_id, rng)?;
+        let unsigned = Transaction::Deploy(placeholder_id, placeholder_owner, Box::new(deployment), fee);
+        let transaction_id = unsigned.to_root()?;
+        let Transaction::Deploy(_, _, deployment, fee) = unsigned else { unreachable!("The transaction is a deployment") };
+        let owner = ProgramOwner::new(private_key, transaction_id.into(), rng)?;
+        Transaction::from_deployment(owner, *deployment, fee)
     }
 }
 
@@ -170,6 +268,7 @@ mod tests {
             random_program,
             random_program_id,
             setup_directory,
+            teardown_directory,
             transfer_to_test_account,
             CREDITS_IMPORT_TEST_PROGRAM,
             HELLO_PROGRAM,
@@ -204,7 +303,17 @@ mod tests {
         thread::sleep(std::time::Duration::from_secs(30));
         let deployment_fee = 200000001;
         let fee_record = record_finder.find_one_record(&recipient_private_key, deployment_fee).unwrap();
-        program_manager.deploy_program("credits_import_test.aleo", deployment_fee, fee_record, None).unwrap();
+        let deployment = program_manager
+            .deploy_program(
+                "credits_import_test.aleo",
+                CreditAmount::from_microcredits(deployment_fee),
+                fee_record,
+                None,
+            )
+            .unwrap();
+        assert_eq!(deployment.program_id, ProgramID::from_str("credits_import_test.aleo").unwrap());
+        assert_eq!(deployment.fee_paid, deployment_fee);
+        assert!(deployment.block_height.is_none());
 
         // Wait for the program to show up on chain
         thread::sleep(std::time::Duration::from_secs(45));
@@ -218,6 +327,10 @@ mod tests {
             println!("Program has not yet appeared on chain, waiting another 15 seconds");
             thread::sleep(std::time::Duration::from_secs(15));
         }
+
+        // Ensure the block the deployment was confirmed in is found
+        let deployment = program_manager.wait_for_deployment(deployment).unwrap();
+        assert!(deployment.block_height.is_some());
     }
 
     #[test]
@@ -244,21 +357,39 @@ mod tests {
 
         let deployment_fee = 200000001;
         // Ensure that deployment fails if the fee is zero
-        let deployment = program_manager.deploy_program(&randomized_program_id, 0, record_5_microcredits.clone(), None);
+        let deployment = program_manager.deploy_program(
+            &randomized_program_id,
+            CreditAmount::from_microcredits(0),
+            record_5_microcredits.clone(),
+            None,
+        );
         assert!(deployment.is_err());
 
         // Ensure that deployment fails if the fee is insufficient
-        let deployment = program_manager.deploy_program(&randomized_program_id, 2, record_5_microcredits.clone(), None);
+        let deployment = program_manager.deploy_program(
+            &randomized_program_id,
+            CreditAmount::from_microcredits(2),
+            record_5_microcredits.clone(),
+            None,
+        );
         assert!(deployment.is_err());
 
         // Ensure that deployment fails if the record used to pay the fee is insufficient
-        let deployment =
-            program_manager.deploy_program(&randomized_program_id, deployment_fee, record_5_microcredits, None);
+        let deployment = program_manager.deploy_program(
+            &randomized_program_id,
+            CreditAmount::from_microcredits(deployment_fee),
+            record_5_microcredits,
+            None,
+        );
         assert!(deployment.is_err());
 
         // Ensure that deployment fails if the program is already on chain
-        let deployment =
-            program_manager.deploy_program("hello.aleo", deployment_fee, record_2000000001_microcredits.clone(), None);
+        let deployment = program_manager.deploy_program(
+            "hello.aleo",
+            CreditAmount::from_microcredits(deployment_fee),
+            record_2000000001_microcredits.clone(),
+            None,
+        );
         assert!(deployment.is_err());
 
         // Ensure that deployment fails if import cannot be found on chain
@@ -275,10 +406,34 @@ mod tests {
 
         let deployment = program_manager.deploy_program(
             &randomized_program_id,
-            deployment_fee,
+            CreditAmount::from_microcredits(deployment_fee),
             record_2000000001_microcredits,
             None,
         );
         assert!(deployment.is_err());
     }
+
+    #[test]
+    fn test_failed_deployment_leaves_program_manager_unchanged() {
+        let private_key = PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap();
+        let fee_record = Record::<Testnet3, Plaintext<Testnet3>>::from_str(RECORD_2000000001_MICROCREDITS).unwrap();
+        let api_client = AleoAPIClient::<Testnet3>::local_testnet3("3030");
+        let program = random_program();
+        let program_id = program.id().to_string();
+        let program_string = format!("import {};\n", random_program_id(16)).add(&program.to_string());
+        let temp_dir = setup_directory("aleo_test_failed_deployment", &program_string, vec![]).unwrap();
+        let mut program_manager =
+            ProgramManager::<Testnet3>::new(Some(private_key), None, Some(api_client), Some(temp_dir.clone())).unwrap();
+        program_manager.add_program(&Program::from_str(HELLO_PROGRAM).unwrap()).unwrap();
+        let programs = program_manager.programs.clone();
+
+        // Ensure a deployment which fails after loading the program from disk leaves no trace of it
+        let deployment =
+            program_manager.deploy_program(&program_id, CreditAmount::from_microcredits(200000001), fee_record, None);
+        assert!(deployment.unwrap_err().to_string().contains("Imported program"));
+        assert!(!program_manager.contains_program(program_id.as_str()).unwrap());
+        assert_eq!(program_manager.programs, programs);
+
+        teardown_directory(&temp_dir);
+    }
 }
@@ -0,0 +1,82 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm_console::prelude::{bech32, FromBase32, FromBytes, ToBase32};
+
+// Human readable prefix of encoded transactions
+const TRANSACTION_PREFIX: &str = "transaction";
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Encode a transaction as a compact string which can be copied between devices, i.e. through
+    /// a QR code, and decoded with [ProgramManager::transaction_from_string].
+    ///
+    /// The transaction's bytes are encoded with bech32m, so the string only contains lowercase
+    /// letters and digits and carries a checksum which detects corrupted copies.
+    pub fn transaction_to_string(&self, transaction: &Transaction<N>) -> Result<String> {
+        let bytes = transaction.to_bytes_le()?;
+        Ok(bech32::encode(TRANSACTION_PREFIX, bytes.to_base32(), bech32::Variant::Bech32m)?)
+    }
+
+    /// Decode a transaction encoded with [ProgramManager::transaction_to_string]
+    pub fn transaction_from_string(transaction: &str) -> Result<Transaction<N>> {
+        let (prefix, data, variant) = bech32::decode(transaction.trim())
+            .map_err(|error| anyhow!("❌ Failed to decode transaction, the string may be corrupted: {error}"))?;
+        ensure!(prefix == TRANSACTION_PREFIX, "❌ Failed to decode transaction: '{prefix}' is an invalid prefix");
+        ensure!(variant == bech32::Variant::Bech32m, "❌ Failed to decode transaction: it is not bech32m encoded");
+        let bytes = Vec::<u8>::from_base32(&data)?;
+        let mut reader = bytes.as_slice();
+        let transaction = Transaction::read_le(&mut reader)
+            .map_err(|error| anyhow!("❌ Failed to decode transaction from its bytes: {error}"))?;
+        ensure!(reader.is_empty(), "❌ Failed to decode transaction: found {} trailing bytes", reader.len());
+        Ok(transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{EXECUTE_TRANSACTION, RECIPIENT_PRIVATE_KEY};
+
+    #[test]
+    fn test_transaction_string_encoding() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+
+        // Ensure the transaction survives a round trip and is encoded more compactly than JSON
+        let encoded = program_manager.transaction_to_string(&transaction).unwrap();
+        assert!(encoded.starts_with("transaction1"));
+        assert!(encoded.len() < EXECUTE_TRANSACTION.len());
+        assert_eq!(ProgramManager::<Testnet3>::transaction_from_string(&encoded).unwrap(), transaction);
+
+        // Ensure corrupted, truncated and mislabelled strings are rejected
+        let mut corrupted = encoded.clone().into_bytes();
+        let middle = corrupted.len() / 2;
+        corrupted[middle] = if corrupted[middle] == b'q' { b'p' } else { b'q' };
+        let corrupted = String::from_utf8(corrupted).unwrap();
+        assert!(ProgramManager::<Testnet3>::transaction_from_string(&corrupted).is_err());
+        let truncated = &encoded[..encoded.len() - 10];
+        assert!(ProgramManager::<Testnet3>::transaction_from_string(truncated).is_err());
+        let data = bech32::decode(&encoded).unwrap().1;
+        let mislabelled = bech32::encode("record", &data, bech32::Variant::Bech32m).unwrap();
+        assert!(ProgramManager::<Testnet3>::transaction_from_string(&mislabelled).is_err());
+        let wrong_variant = bech32::encode(TRANSACTION_PREFIX, &data, bech32::Variant::Bech32).unwrap();
+        assert!(ProgramManager::<Testnet3>::transaction_from_string(&wrong_variant).is_err());
+        assert!(ProgramManager::<Testnet3>::transaction_from_string(EXECUTE_TRANSACTION).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::{
+    circuit::Aleo,
+    synthesizer::{Function, Process},
+};
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Evaluate a program function locally and return its outputs without building a transaction.
+    ///
+    /// The program and its imports are found in the program manager, on disk or on the Aleo
+    /// Network. Only functions without a finalize block can be evaluated, since the on-chain
+    /// effects of a finalize block can only be applied by executing the function with
+    /// [ProgramManager::execute_program]. The function is evaluated with the circuit `A` of the
+    /// network, i.e. `AleoV0` for testnet3. A program manager without a signing key, i.e. one
+    /// created with [ProgramManager::read_only], evaluates the function as a freshly generated
+    /// account, so `self.caller` is that account's address.
+    pub fn evaluate_program<A: Aleo<Network = N>>(
+        &self,
+        program_id: impl TryInto<ProgramID<N>>,
+        function: impl TryInto<Identifier<N>>,
+        inputs: impl ExactSizeIterator<Item = impl TryInto<Value<N>>>,
+        password: Option<&str>,
+    ) -> Result<Vec<Value<N>>> {
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        let function_id = function.try_into().map_err(|_| anyhow!("Invalid function name"))?;
+        let inputs = inputs
+            .map(|input| input.try_into().map_err(|_| anyhow!("Invalid input")))
+            .collect::<Result<Vec<Value<N>>>>()?;
+        ensure!(
+            self.load_function(&program_id, &function_id)?.finalize().is_none(),
+            "❌ Function {function_id:?} from program {program_id:?} has a finalize block which can change on-chain state, use execute_program instead"
+        );
+
+        // Nothing is broadcast, so a program manager without a signing key evaluates the function
+        // as a throwaway account
+        let rng = &mut rand::thread_rng();
+        let private_key = match self.get_private_key(password) {
+            Err(error) if error.is::<NoSigningKey>() => PrivateKey::new(rng)?,
+            private_key => private_key?,
+        };
+
+        // Load the program and its imports into a process
+        let mut process = Process::<N>::load()?;
+        self.add_program_to_process(&mut process, &program_id)?;
+
+        // Evaluate the function with the circuit of the network
+        let authorization =
+            process.authorize::<A, _>(&private_key, program_id, function_id, inputs.into_iter(), rng)?;
+        Ok(process.evaluate::<A>(authorization)?.outputs().to_vec())
+    }
+
+    // Find a function in a program known to the program manager, on disk or on the Aleo Network.
+    // credits.aleo is part of snarkVM, so it is always found.
+    pub(crate) fn load_function(&self, program_id: &ProgramID<N>, function_id: &Identifier<N>) -> Result<Function<N>> {
+        let program = self
+            .get_program(*program_id)
+            .or_else(|_| {
+                if program_id.to_string() == "credits.aleo" {
+                    Program::credits()
+                } else {
+                    self.find_program(program_id)
+                }
+            })
+            .map_err(|_| anyhow!("❌ Program {program_id:?} could not be found locally or on the Aleo Network"))?;
+        program.get_function(function_id)
+    }
+
+    // Load a program and its imports into a process
+    fn add_program_to_process(&self, process: &mut Process<N>, program_id: &ProgramID<N>) -> Result<()> {
+        if process.contains_program(program_id) {
+            return Ok(());
+        }
+        let program = self
+            .get_program(*program_id)
+            .or_else(|_| self.find_program(program_id))
+            .map_err(|_| anyhow!("❌ Program {program_id:?} could not be found locally or on the Aleo Network"))?;
+        program.imports().keys().try_for_each(|import_id| self.add_program_to_process(process, import_id))?;
+        process.add_program(&program)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "wasm"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::{HELLO_PROGRAM, RECIPIENT_PRIVATE_KEY};
+
+    use snarkvm::circuit::AleoV0;
+
+    const FINALIZE_PROGRAM: &str = r"program finalize_test.aleo;
+
+mapping counts:
+    key left as address.public;
+    value right as u64.public;
+
+function increment:
+    input r0 as u64.public;
+    finalize self.caller r0;
+
+finalize increment:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_init counts[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into counts[r0];
+";
+
+    fn program_manager(program: &str) -> ProgramManager<Testnet3> {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        program_manager.add_program(&Program::from_str(program).unwrap()).unwrap();
+        program_manager
+    }
+
+    #[test]
+    fn test_evaluate_program_rejects_state_changing_functions() {
+        let program_manager = program_manager(FINALIZE_PROGRAM);
+        let error = program_manager
+            .evaluate_program::<AleoV0>("finalize_test.aleo", "increment", ["5u64"].into_iter(), None)
+            .unwrap_err();
+        assert!(error.to_string().contains("finalize"));
+
+        // Ensure unknown functions are rejected
+        assert!(
+            program_manager
+                .evaluate_program::<AleoV0>("finalize_test.aleo", "decrement", ["5u64"].into_iter(), None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_evaluate_program() {
+        let program_manager = program_manager(HELLO_PROGRAM);
+        let outputs = program_manager
+            .evaluate_program::<AleoV0>("hello.aleo", "main", ["5u32", "5u32"].into_iter(), None)
+            .unwrap();
+        assert_eq!(outputs, vec![Value::from_str("10u32").unwrap()]);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_evaluate_program_without_a_signing_key() {
+        let mut program_manager = ProgramManager::<Testnet3>::read_only(None, None).unwrap();
+        program_manager.add_program(&Program::from_str(HELLO_PROGRAM).unwrap()).unwrap();
+        let outputs = program_manager
+            .evaluate_program::<AleoV0>("hello.aleo", "main", ["5u32", "5u32"].into_iter(), None)
+            .unwrap();
+        assert_eq!(outputs, vec![Value::from_str("10u32").unwrap()]);
+    }
+}
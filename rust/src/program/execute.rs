@@ -16,7 +16,7 @@
 
 use super::*;
 
-impl<N: Network> ProgramManager<N> {
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
     /// Execute a program function on the Aleo Network.
     ///
     /// To run this function successfully, the program must already be deployed on the Aleo Network
@@ -25,11 +25,24 @@ impl<N: Network> ProgramManager<N> {
         program_id: impl TryInto<ProgramID<N>>,
         function: impl TryInto<Identifier<N>>,
         inputs: impl ExactSizeIterator<Item = impl TryInto<Value<N>>>,
-        fee: u64,
+        fee: CreditAmount,
         fee_record: Record<N, Plaintext<N>>,
         password: Option<&str>,
     ) -> Result<String> {
+        let fee = fee.microcredits();
         ensure!(fee > 0, "Fee must be greater than 0");
+        InsufficientFunds::check(&[&fee_record], fee)?;
+
+        // Reserve the fee record and the records spent as inputs of the function
+        let inputs = inputs
+            .map(|input| input.try_into().map_err(|_| anyhow!("Invalid input")))
+            .collect::<Result<Vec<Value<N>>>>()?;
+        let mut records = vec![&fee_record];
+        records.extend(inputs.iter().filter_map(|input| match input {
+            Value::Record(record) => Some(record),
+            _ => None,
+        }));
+        let _reservation = self.reserve_records(&records)?;
 
         // Ensure network config is set, otherwise execution is not possible
         ensure!(
@@ -54,14 +67,15 @@ impl<N: Network> ProgramManager<N> {
         // Attempt to construct the execution transaction
         println!("Building transaction..");
         let query = self.api_client.as_ref().unwrap().base_url();
-        let transaction = Self::create_execute_transaction(
+        let transaction = Self::build_execute_transaction(
             &private_key,
             fee,
-            inputs,
+            inputs.into_iter(),
             fee_record,
             &program,
             function_id,
             query.to_string(),
+            &|phase| self.report_progress(phase),
         )?;
 
         // Broadcast the execution transaction to the network
@@ -87,6 +101,21 @@ impl<N: Network> ProgramManager<N> {
         program: &Program<N>,
         function: impl TryInto<Identifier<N>>,
         query: String,
+    ) -> Result<Transaction<N>> {
+        Self::build_execute_transaction(private_key, fee, inputs, fee_record, program, function, query, &|_| ())
+    }
+
+    // Create an execute transaction, reporting the phases of the execution
+    #[allow(clippy::too_many_arguments)]
+    fn build_execute_transaction(
+        private_key: &PrivateKey<N>,
+        fee: u64,
+        inputs: impl ExactSizeIterator<Item = impl TryInto<Value<N>>>,
+        fee_record: Record<N, Plaintext<N>>,
+        program: &Program<N>,
+        function: impl TryInto<Identifier<N>>,
+        query: String,
+        progress: &dyn Fn(TransactionPhase),
     ) -> Result<Transaction<N>> {
         // Initialize an RNG and query object for the transaction
         let rng = &mut rand::thread_rng();
@@ -104,11 +133,13 @@ impl<N: Network> ProgramManager<N> {
         let store = ConsensusStore::<N, ConsensusMemory<N>>::open(None)?;
         let vm = VM::<N, ConsensusMemory<N>>::from(store)?;
         if &program.id().to_string() != "credits.aleo" {
+            progress(TransactionPhase::Synthesizing);
             let deployment = vm.deploy(program, rng)?;
             vm.process().write().finalize_deployment(vm.program_store(), &deployment)?;
         };
 
         // Create a new execution transaction.
+        progress(TransactionPhase::Proving);
         Transaction::execute(
             &vm,
             private_key,
@@ -147,7 +178,7 @@ mod tests {
                 "credits_import_test.aleo",
                 "test",
                 ["1312u32", "62131112u32"].into_iter(),
-                500_000,
+                CreditAmount::from_microcredits(500_000),
                 fee_record,
                 None,
             );
@@ -168,7 +199,7 @@ mod tests {
                 "credits_import_test.aleo",
                 "test",
                 ["1337u32", "42u32"].into_iter(),
-                500000,
+                CreditAmount::from_microcredits(500000),
                 fee_record,
                 Some("password"),
             );
@@ -196,19 +227,32 @@ mod tests {
             "hello.aleo",
             "main",
             ["5u32", "6u32"].into_iter(),
-            500000,
-            record_5_microcredits,
+            CreditAmount::from_microcredits(500000),
+            record_5_microcredits.clone(),
             None,
         );
 
         assert!(execution.is_err());
 
+        // Assert that execution fails if a record input is in use by another pending operation
+        let _reservation = program_manager.reserve_records(&[&record_5_microcredits]).unwrap();
+        let execution = program_manager.execute_program(
+            "hello.aleo",
+            "main",
+            [Value::Record(record_5_microcredits), Value::from_str("6u32").unwrap()].into_iter(),
+            CreditAmount::from_microcredits(200),
+            record_2000000001_microcredits.clone(),
+            None,
+        );
+
+        assert!(execution.unwrap_err().to_string().contains("already in use"));
+
         // Assert that execution fails if a fee is specified but no records are
         let execution = program_manager.execute_program(
             "hello.aleo",
             "main",
             ["5u32", "6u32"].into_iter(),
-            200,
+            CreditAmount::from_microcredits(200),
             record_2000000001_microcredits.clone(),
             None,
         );
@@ -221,7 +265,7 @@ mod tests {
             &randomized_program_id,
             "main",
             ["5u32", "6u32"].into_iter(),
-            500000,
+            CreditAmount::from_microcredits(500000),
             record_2000000001_microcredits.clone(),
             None,
         );
@@ -233,7 +277,7 @@ mod tests {
             "hello.aleo",
             "random_function",
             ["5u32", "6u32"].into_iter(),
-            500000,
+            CreditAmount::from_microcredits(500000),
             record_2000000001_microcredits.clone(),
             None,
         );
@@ -245,7 +289,7 @@ mod tests {
             "hello.aleo",
             "random_function",
             ["5u32", "6u32"].into_iter(),
-            500000,
+            CreditAmount::from_microcredits(500000),
             record_2000000001_microcredits,
             None,
         );
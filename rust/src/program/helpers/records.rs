@@ -18,12 +18,12 @@ use super::*;
 
 /// Helper struct for finding records on chain during program development
 #[derive(Clone)]
-pub struct RecordFinder<N: Network> {
-    api_client: AleoAPIClient<N>,
+pub struct RecordFinder<N: Network, T: HttpTransport = UreqTransport> {
+    api_client: AleoAPIClient<N, T>,
 }
 
-impl<N: Network> RecordFinder<N> {
-    pub fn new(api_client: AleoAPIClient<N>) -> Self {
+impl<N: Network, T: HttpTransport> RecordFinder<N, T> {
+    pub fn new(api_client: AleoAPIClient<N, T>) -> Self {
         Self { api_client }
     }
 
@@ -0,0 +1,257 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use serde::Serialize;
+use std::{fs, path::Path};
+
+// Number of blocks requested at a time, which is the most the node serves in one request
+const HISTORY_BATCH_SIZE: u32 = 50;
+
+/// The file format of a transaction history exported with
+/// [ProgramManager::export_transaction_history]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryFormat {
+    /// One line per transaction with a header line. Lists are separated by spaces and fees paid
+    /// by other accounts are left empty.
+    Csv,
+    /// An array of objects with the fields of [HistoryEntry]
+    Json,
+}
+
+/// A transaction involving the program manager's account, found with
+/// [ProgramManager::transaction_history]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HistoryEntry<N: Network> {
+    /// The height of the block holding the transaction
+    pub block_height: u32,
+    /// The timestamp of the block holding the transaction, in seconds since the Unix epoch
+    pub timestamp: i64,
+    /// The ID of the transaction
+    pub transaction_id: N::TransactionID,
+    /// The functions executed by the transaction, i.e. `credits.aleo/transfer`
+    pub functions: Vec<String>,
+    /// The microcredits received by the account in records created by other accounts
+    pub received: u64,
+    /// The microcredits the account transferred to other addresses
+    pub sent: u64,
+    /// The addresses the account transferred microcredits to
+    pub counterparties: Vec<Address<N>>,
+    /// The fee paid by the account in microcredits, or `None` if another account paid it
+    pub fee: Option<u64>,
+}
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Find the transactions of a range of blocks involving the program manager's account, i.e.
+    /// for bookkeeping.
+    ///
+    /// A transaction involves the account if it calls a function with the account or creates
+    /// records owned by the account. The amounts come from the records the account can decrypt:
+    /// records received from other accounts and the private inputs of the `credits.aleo/transfer`
+    /// calls made by the account. The sender of a private transfer can't be recovered by its
+    /// recipient, so only the recipients of the transfers made by the account are listed as
+    /// counterparties.
+    pub fn transaction_history(
+        &self,
+        block_heights: Range<u32>,
+        password: Option<&str>,
+    ) -> Result<Vec<HistoryEntry<N>>> {
+        ensure!(!block_heights.is_empty(), "❌ The range of block heights to scan is empty");
+        let api_client = self.api_client()?;
+        let view_key = ViewKey::try_from(self.get_private_key(password)?)?;
+        let mut entries = vec![];
+        for start_height in block_heights.clone().step_by(HISTORY_BATCH_SIZE as usize) {
+            let end_height = block_heights.end.min(start_height.saturating_add(HISTORY_BATCH_SIZE));
+            for block in api_client.get_blocks(start_height, end_height)? {
+                entries.extend(self.block_history(&block, &view_key, password)?);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Write the transactions of a range of blocks involving the program manager's account to a
+    /// CSV or JSON file, see [ProgramManager::transaction_history]
+    pub fn export_transaction_history(
+        &self,
+        block_heights: Range<u32>,
+        path: impl AsRef<Path>,
+        format: HistoryFormat,
+        password: Option<&str>,
+    ) -> Result<()> {
+        let entries = self.transaction_history(block_heights, password)?;
+        let contents = match format {
+            HistoryFormat::Csv => Self::history_to_csv(&entries),
+            HistoryFormat::Json => serde_json::to_string_pretty(&entries)?,
+        };
+        fs::write(path.as_ref(), contents)
+            .map_err(|error| anyhow!("❌ Failed to write the transaction history to {:?}: {error}", path.as_ref()))
+    }
+
+    // Find the transactions of a block involving the account of a view key
+    fn block_history(
+        &self,
+        block: &Block<N>,
+        view_key: &ViewKey<N>,
+        password: Option<&str>,
+    ) -> Result<Vec<HistoryEntry<N>>> {
+        let address = view_key.to_address();
+        let mut entries = vec![];
+        for transaction in block.transactions().iter() {
+            let mut entry = HistoryEntry {
+                block_height: block.height(),
+                timestamp: block.timestamp(),
+                transaction_id: transaction.id(),
+                functions: vec![],
+                received: 0,
+                sent: 0,
+                counterparties: vec![],
+                fee: None,
+            };
+            let mut involved = false;
+            for transition in transaction.transitions() {
+                let (program_id, function_name) = (transition.program_id(), transition.function_name());
+                entry.functions.push(format!("{program_id}/{function_name}"));
+                let is_caller = Self::caller_transition_view_key(transition, view_key)?.is_some();
+                involved |= is_caller;
+
+                if !is_caller {
+                    // Records of other programs don't hold credits, but still involve the account
+                    for (_, record) in transition.records().filter(|(_, record)| record.is_owner(view_key)) {
+                        let microcredits = record.decrypt(view_key)?.microcredits().unwrap_or(0);
+                        entry.received = entry.received.saturating_add(microcredits);
+                        involved = true;
+                    }
+                } else if program_id.to_string() == "credits.aleo" {
+                    match function_name.to_string().as_str() {
+                        "fee" => entry.fee = Some(*transaction.fee()?),
+                        "transfer" => {
+                            // The recipient and the amount are the private inputs of the transfer
+                            let decoded = self.decode_transition(transition, password)?;
+                            let values = decoded.inputs.iter().map(|input| input.value.as_ref()).collect::<Vec<_>>();
+                            if let [_, Some(Value::Plaintext(recipient)), Some(Value::Plaintext(amount))] = values[..] {
+                                if let (
+                                    Plaintext::Literal(Literal::Address(recipient), _),
+                                    Plaintext::Literal(Literal::U64(amount), _),
+                                ) = (recipient, amount)
+                                {
+                                    if *recipient != address {
+                                        entry.sent = entry.sent.saturating_add(**amount);
+                                        entry.counterparties.push(*recipient);
+                                    }
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if involved {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    // Format history entries as CSV
+    fn history_to_csv(entries: &[HistoryEntry<N>]) -> String {
+        let mut csv = "block_height,timestamp,transaction_id,functions,received,sent,counterparties,fee\n".to_string();
+        for entry in entries {
+            let counterparties = entry.counterparties.iter().map(|address| address.to_string()).collect::<Vec<_>>();
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                entry.block_height,
+                entry.timestamp,
+                entry.transaction_id,
+                entry.functions.join(" "),
+                entry.received,
+                entry.sent,
+                counterparties.join(" "),
+                entry.fee.map_or(String::new(), |fee| fee.to_string())
+            ));
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{BEACON_PRIVATE_KEY, EXECUTE_TRANSACTION, RECIPIENT_PRIVATE_KEY};
+
+    use snarkvm::{parameters::testnet3::GenesisBytes, synthesizer::Transactions};
+    use snarkvm_console::prelude::FromBytes;
+
+    #[test]
+    fn test_transaction_history() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let genesis = Block::<Testnet3>::from_bytes_le(GenesisBytes::load_bytes()).unwrap();
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+        let block = Block::new(
+            &private_key,
+            genesis.hash(),
+            *genesis.header(),
+            Transactions::from(&[transaction.clone()]),
+            None,
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+
+        // Ensure the transaction made by the account is listed with the fee it paid. Its only
+        // record is the change of the fee, which isn't counted as received.
+        let entries = program_manager.block_history(&block, &view_key, None).unwrap();
+        let expected = HistoryEntry {
+            block_height: block.height(),
+            timestamp: block.timestamp(),
+            transaction_id: transaction.id(),
+            functions: vec!["hello.aleo/main".to_string(), "credits.aleo/fee".to_string()],
+            received: 0,
+            sent: 0,
+            counterparties: vec![],
+            fee: Some(2000000),
+        };
+        assert_eq!(entries, vec![expected.clone()]);
+
+        // Ensure the history is formatted as CSV with empty fields for missing values
+        let csv =
+            ProgramManager::<Testnet3>::history_to_csv(&[expected.clone(), HistoryEntry { fee: None, ..expected }]);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "block_height,timestamp,transaction_id,functions,received,sent,counterparties,fee");
+        let row = format!(
+            "{},{},{},hello.aleo/main credits.aleo/fee,0,0,,",
+            block.height(),
+            block.timestamp(),
+            transaction.id()
+        );
+        assert_eq!(lines[1], format!("{row}2000000"));
+        assert_eq!(lines[2], row);
+
+        // Ensure the history is formatted as JSON with the fields of the entries
+        let json = serde_json::to_value(&entries).unwrap();
+        assert_eq!(json[0]["transaction_id"], transaction.id().to_string());
+        assert_eq!(json[0]["functions"][1], "credits.aleo/fee");
+        assert_eq!(json[0]["fee"], 2000000);
+
+        // Ensure transactions not involving the account are left out
+        let observer_key = PrivateKey::<Testnet3>::from_str(BEACON_PRIVATE_KEY).unwrap();
+        let observer = ProgramManager::<Testnet3>::new(Some(observer_key), None, None, None).unwrap();
+        let observer_view_key = ViewKey::try_from(&observer_key).unwrap();
+        assert!(observer.block_history(&block, &observer_view_key, None).unwrap().is_empty());
+        assert!(program_manager.transaction_history(5..5, None).is_err());
+    }
+}
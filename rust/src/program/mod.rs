@@ -18,55 +18,169 @@
 
 use super::*;
 
+use std::fmt;
+
+pub mod backup;
+pub use backup::*;
+
+pub mod commitment;
+pub use commitment::*;
+
+pub mod contacts;
+pub use contacts::*;
+
+pub mod decode;
+pub use decode::*;
+
+pub mod dependencies;
+pub use dependencies::*;
+
 pub mod deploy;
 pub use deploy::*;
 
+pub mod encoding;
+pub use encoding::*;
+
+pub mod evaluate;
+pub use evaluate::*;
+
 pub mod execute;
 pub use execute::*;
 
 pub mod helpers;
 pub use helpers::*;
 
+pub mod history;
+pub use history::*;
+
 pub mod network;
 pub use network::*;
 
+pub mod outputs;
+pub use outputs::*;
+
+pub mod parse;
+pub use parse::*;
+
+pub mod progress;
+pub use progress::*;
+
+pub mod reservations;
+pub(crate) use reservations::*;
+
 pub mod resolver;
 pub use resolver::*;
 
+pub mod resumable;
+pub use resumable::*;
+
+pub mod schema;
+pub use schema::*;
+
 pub mod transfer;
 pub use transfer::*;
 
+pub mod verify;
+pub use verify::*;
+
+pub mod watch;
+pub use watch::*;
+
+/// Error returned when an operation which signs is run by a program manager created with
+/// [ProgramManager::read_only]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoSigningKey;
+
+impl fmt::Display for NoSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "No signing key is configured, the program manager is read-only")
+    }
+}
+
+impl std::error::Error for NoSigningKey {}
+
+/// Error returned before a transaction is built when the records supplied to a deployment,
+/// execution or transfer hold fewer microcredits than it spends, so no time is spent proving a
+/// transaction the network would reject. Testnet3 has no public balances, so these records are
+/// the only funds available to the operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InsufficientFunds {
+    /// The microcredits the operation spends
+    pub required: u64,
+    /// The microcredits held by the records supplied to the operation
+    pub available: u64,
+}
+
+impl InsufficientFunds {
+    // Fail if the records hold fewer microcredits than required
+    pub(crate) fn check<N: Network>(records: &[&Record<N, Plaintext<N>>], required: u64) -> Result<()> {
+        let available = records.iter().try_fold(0u64, |total, record| {
+            record.microcredits().map(|microcredits| total.saturating_add(microcredits))
+        })?;
+        if available < required {
+            return Err(Self { required, available }.into());
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for InsufficientFunds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "❌ Insufficient funds: {} microcredits are required, the records supplied hold {} microcredits",
+            self.required, self.available
+        )
+    }
+}
+
+impl std::error::Error for InsufficientFunds {}
+
 /// Program management object for loading programs for building, execution, and deployment
 ///
 /// This object is meant to be a software abstraction that can be consumed by software like
 /// CLI tools, IDE plugins, Server-side stack components and other software that needs to
 /// interact with the Aleo network.
 #[derive(Clone)]
-pub struct ProgramManager<N: Network> {
+pub struct ProgramManager<N: Network, T: HttpTransport = UreqTransport> {
     pub(crate) programs: IndexMap<ProgramID<N>, Program<N>>,
     pub(crate) private_key: Option<PrivateKey<N>>,
     pub(crate) private_key_ciphertext: Option<Ciphertext<N>>,
     pub(crate) local_program_directory: Option<PathBuf>,
-    pub(crate) api_client: Option<AleoAPIClient<N>>,
+    pub(crate) api_client: Option<AleoAPIClient<N, T>>,
+    pub(crate) progress_callback: Option<ProgressCallback>,
+    pub(crate) reserved_records: ReservedRecords<N>,
+    pub(crate) contacts: IndexMap<String, Address<N>>,
 }
 
-impl<N: Network> ProgramManager<N> {
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
     /// Create a new program manager by specifying custom options for the private key (or private
     /// key ciphertext) and resolver. Use this method if you want to create a custom resolver
     /// (i.e. one that searches a local or remote database) for program and record resolution.
     pub fn new(
         private_key: Option<PrivateKey<N>>,
         private_key_ciphertext: Option<Ciphertext<N>>,
-        api_client: Option<AleoAPIClient<N>>,
+        api_client: Option<AleoAPIClient<N, T>>,
         local_program_directory: Option<PathBuf>,
     ) -> Result<Self> {
         if private_key.is_some() && private_key_ciphertext.is_some() {
             bail!("Cannot have both private key and private key ciphertext");
         } else if private_key.is_none() && private_key_ciphertext.is_none() {
-            bail!("Must have either private key or private key ciphertext");
+            bail!(
+                "Must have either private key or private key ciphertext, use ProgramManager::read_only to create a program manager which never signs"
+            );
         }
         let programs = IndexMap::new();
-        Ok(Self { programs, private_key, private_key_ciphertext, local_program_directory, api_client })
+        Ok(Self {
+            programs,
+            private_key,
+            private_key_ciphertext,
+            local_program_directory,
+            api_client,
+            progress_callback: None,
+            reserved_records: Default::default(),
+            contacts: IndexMap::new(),
+        })
     }
 
     /// Manually add a program to the program manager from memory if it does not already exist
@@ -96,11 +210,29 @@ impl<N: Network> ProgramManager<N> {
         Ok(self.programs.contains_key(&program_id))
     }
 
+    /// Compute the address of a program, which is derived from its ID.
+    ///
+    /// The address identifies the program on chain, i.e. as the caller of the programs it
+    /// calls, and no private key exists for it.
+    pub fn program_address(&self, program_id: impl TryInto<ProgramID<N>>) -> Result<Address<N>> {
+        let program_id = program_id.try_into().map_err(|_| anyhow!("❌ Invalid program ID"))?;
+        program_id.to_address()
+    }
+
+    /// List the IDs of the programs loaded in the program manager, in the order they were added.
+    ///
+    /// The program manager doesn't keep a VM between transactions, so these are the programs
+    /// available to it without looking them up on disk or on the Aleo Network. An import missing
+    /// from this list has to be resolved from one of those sources when a transaction is built.
+    pub fn loaded_programs(&self) -> Vec<String> {
+        self.programs.keys().map(|program_id| program_id.to_string()).collect()
+    }
+
     /// Get the private key from the program manager. If the key is stored as ciphertext, a
     /// password must be provided to decrypt it
     pub(super) fn get_private_key(&self, password: Option<&str>) -> Result<PrivateKey<N>> {
         if self.private_key.is_none() && self.private_key_ciphertext.is_none() {
-            bail!("Private key is not configured");
+            return Err(NoSigningKey.into());
         };
         if let Some(private_key) = &self.private_key {
             if self.private_key_ciphertext.is_some() {
@@ -120,6 +252,67 @@ impl<N: Network> ProgramManager<N> {
         };
         bail!("Private key configuration error")
     }
+
+    /// Change the password of the private key ciphertext stored in the program manager.
+    ///
+    /// The ciphertext is decrypted with the old password and encrypted again with the new one,
+    /// so the private key is never stored in plaintext. The program manager is left unchanged if
+    /// the old password is wrong.
+    pub fn rekey(&mut self, old_password: &str, new_password: &str) -> Result<()> {
+        ensure!(
+            self.private_key_ciphertext.is_some(),
+            "❌ The program manager does not have a private key ciphertext to rekey"
+        );
+        ensure!(!new_password.is_empty(), "❌ The new password must not be empty");
+        let private_key = self.get_private_key(Some(old_password)).map_err(|_| {
+            anyhow!("❌ Failed to decrypt the private key ciphertext, the old password may be incorrect")
+        })?;
+        self.private_key_ciphertext = Some(Encryptor::encrypt_private_key_with_secret(&private_key, new_password)?);
+        Ok(())
+    }
+}
+
+impl<N: Network> ProgramManager<N> {
+    /// Create a program manager for a freshly generated random account, returning the private
+    /// key of the account alongside it so it can be displayed to the user or backed up. The
+    /// parameters of the network config are installed, see [NetworkConfig::install_parameters].
+    pub fn new_account(
+        network_config: Option<&NetworkConfig>,
+        local_program_directory: Option<PathBuf>,
+    ) -> Result<(Self, PrivateKey<N>)> {
+        let (private_key, ..) = generate_account::<N>()?;
+        let api_client = Self::api_client_from_config(network_config)?;
+        let program_manager = Self::new(Some(private_key), None, api_client, local_program_directory)?;
+        Ok((program_manager, private_key))
+    }
+
+    /// Create a program manager without a private key for software which never signs, such as
+    /// explorers and analytics tools.
+    ///
+    /// Programs can be resolved, evaluated and verified and the network can be queried, but every
+    /// operation which needs to sign a transaction fails with a [NoSigningKey] error. The
+    /// parameters of the network config are installed, see [NetworkConfig::install_parameters].
+    pub fn read_only(network_config: Option<&NetworkConfig>, local_program_directory: Option<PathBuf>) -> Result<Self> {
+        let api_client = Self::api_client_from_config(network_config)?;
+        Ok(Self {
+            programs: IndexMap::new(),
+            private_key: None,
+            private_key_ciphertext: None,
+            local_program_directory,
+            api_client,
+            progress_callback: None,
+            reserved_records: Default::default(),
+            contacts: IndexMap::new(),
+        })
+    }
+
+    // Create the API client of a network config, installing the parameters of the config so the
+    // VMs built by the program manager load them instead of downloading them
+    fn api_client_from_config(network_config: Option<&NetworkConfig>) -> Result<Option<AleoAPIClient<N>>> {
+        let Some(network_config) = network_config else { return Ok(None) };
+        network_config.install_parameters()?;
+        Ok(Some(AleoAPIClient::from_config(network_config)?))
+    }
 }
 
 #[cfg(test)]
@@ -127,10 +320,51 @@ mod tests {
 
     use super::*;
     use crate::{
-        test_utils::{HELLO_PROGRAM, HELLO_PROGRAM_2},
+        test_utils::{
+            HELLO_PROGRAM,
+            HELLO_PROGRAM_2,
+            RECIPIENT_ADDRESS,
+            RECORD_2000000001_MICROCREDITS,
+            RECORD_5_MICROCREDITS,
+        },
         RECIPIENT_PRIVATE_KEY,
     };
 
+    #[test]
+    fn test_program_address() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+
+        // Ensure each program has its own address, which is the same every time it is computed
+        let address = program_manager.program_address("credits.aleo").unwrap();
+        assert_eq!(program_manager.program_address("credits.aleo").unwrap(), address);
+        assert_ne!(program_manager.program_address("hello.aleo").unwrap(), address);
+        assert!(program_manager.program_address("credits").is_err());
+    }
+
+    #[test]
+    fn test_rekey() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let ciphertext = Encryptor::<Testnet3>::encrypt_private_key_with_secret(&private_key, "old").unwrap();
+        let mut program_manager = ProgramManager::<Testnet3>::new(None, Some(ciphertext.clone()), None, None).unwrap();
+
+        // Ensure a wrong old password or an empty new password leaves the ciphertext unchanged
+        let error = program_manager.rekey("wrong", "new").unwrap_err();
+        assert!(error.to_string().contains("old password may be incorrect"));
+        assert!(program_manager.rekey("old", "").is_err());
+        assert_eq!(program_manager.private_key_ciphertext, Some(ciphertext.clone()));
+
+        // Ensure the key is only decrypted with the new password after rekeying
+        program_manager.rekey("old", "new").unwrap();
+        assert_ne!(program_manager.private_key_ciphertext, Some(ciphertext));
+        assert_eq!(program_manager.get_private_key(Some("new")).unwrap(), private_key);
+        assert!(program_manager.get_private_key(Some("old")).is_err());
+
+        // Ensure program managers holding a plaintext private key can't be rekeyed
+        let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        assert!(program_manager.rekey("old", "new").is_err());
+    }
+
     #[test]
     fn test_constructors_fail_with_multiple_keys_or_no_keys() {
         let api_client = AleoAPIClient::<Testnet3>::testnet3();
@@ -169,6 +403,80 @@ mod tests {
         assert!(program_manager.is_ok());
     }
 
+    #[test]
+    fn test_new_account() {
+        let network_config = NetworkConfig::local_testnet3("3030");
+        let (program_manager, private_key) =
+            ProgramManager::<Testnet3>::new_account(Some(&network_config), None).unwrap();
+
+        // Ensure the program manager uses the generated account and network config
+        assert_eq!(program_manager.get_private_key(None).unwrap(), private_key);
+        assert_eq!(program_manager.api_client().unwrap().network_config(), network_config);
+
+        // Ensure a different account is generated each time
+        let (program_manager, other_private_key) = ProgramManager::<Testnet3>::new_account(None, None).unwrap();
+        assert_ne!(private_key, other_private_key);
+        assert!(program_manager.api_client().is_err());
+
+        // Ensure the parameters of the network config are installed
+        let parameters_dir = std::env::temp_dir().join("aleo_test_new_account_parameters");
+        let network_config = network_config.with_parameters_dir(&parameters_dir);
+        let error = ProgramManager::<Testnet3>::new_account(Some(&network_config), None).err().unwrap();
+        assert!(error.to_string().contains("does not exist"));
+        std::fs::create_dir_all(&parameters_dir).unwrap();
+        assert!(ProgramManager::<Testnet3>::new_account(Some(&network_config), None).is_ok());
+        assert!(ProgramManager::<Testnet3>::read_only(Some(&network_config), None).is_ok());
+        std::fs::remove_dir_all(&parameters_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_only() {
+        let network_config = NetworkConfig::local_testnet3("3030");
+        let mut program_manager = ProgramManager::<Testnet3>::read_only(Some(&network_config), None).unwrap();
+        assert_eq!(program_manager.api_client().unwrap().network_config(), network_config);
+
+        // Ensure programs can still be managed
+        let program = Program::<Testnet3>::from_str(HELLO_PROGRAM).unwrap();
+        program_manager.add_program(&program).unwrap();
+        assert_eq!(program_manager.get_program("hello.aleo").unwrap(), program);
+
+        // Ensure operations which sign fail with a NoSigningKey error
+        let error = program_manager.get_private_key(Some("password")).unwrap_err();
+        assert_eq!(error.downcast_ref::<NoSigningKey>(), Some(&NoSigningKey));
+        let input_record = Record::<Testnet3, Plaintext<Testnet3>>::from_str(RECORD_2000000001_MICROCREDITS).unwrap();
+        let fee_record = Record::<Testnet3, Plaintext<Testnet3>>::from_str(RECORD_5_MICROCREDITS).unwrap();
+        let recipient = Address::from_str(RECIPIENT_ADDRESS).unwrap();
+        let error = program_manager
+            .plan_transfer(1, CreditAmount::from_microcredits(1), recipient, None, input_record, fee_record)
+            .unwrap_err();
+        assert!(error.is::<NoSigningKey>());
+    }
+
+    #[test]
+    fn test_insufficient_funds() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let api_client = AleoAPIClient::<Testnet3>::local_testnet3("3030");
+        let mut program_manager =
+            ProgramManager::<Testnet3>::new(Some(private_key), None, Some(api_client), None).unwrap();
+        let record = Record::<Testnet3, Plaintext<Testnet3>>::from_str(RECORD_5_MICROCREDITS).unwrap();
+        let other_record = Record::<Testnet3, Plaintext<Testnet3>>::from_str(RECORD_2000000001_MICROCREDITS).unwrap();
+        let insufficient_funds = |error: anyhow::Error| error.downcast::<InsufficientFunds>().unwrap();
+
+        // Ensure the total held by the records is compared with the amount spent
+        assert!(InsufficientFunds::check(&[&record, &other_record], 2000000006).is_ok());
+        let error = InsufficientFunds::check(&[&record, &other_record], 2000000007).unwrap_err();
+        assert_eq!(insufficient_funds(error), InsufficientFunds { required: 2000000007, available: 2000000006 });
+
+        // Ensure executions and deployments are stopped before a transaction is built
+        let fee = CreditAmount::from_microcredits(6);
+        let error = program_manager
+            .execute_program("hello.aleo", "main", ["5u32", "5u32"].into_iter(), fee, record.clone(), None)
+            .unwrap_err();
+        assert_eq!(insufficient_funds(error), InsufficientFunds { required: 6, available: 5 });
+        let error = program_manager.deploy_program("hello.aleo", fee, record, None).unwrap_err();
+        assert_eq!(insufficient_funds(error), InsufficientFunds { required: 6, available: 5 });
+    }
+
     #[test]
     fn test_program_management_methods() {
         let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
@@ -177,8 +485,10 @@ mod tests {
         // Test program addition
         let program = Program::<Testnet3>::from_str(HELLO_PROGRAM).unwrap();
         assert!(!program_manager.contains_program(program.id()).unwrap());
+        assert!(program_manager.loaded_programs().is_empty());
         program_manager.add_program(&program).unwrap();
         assert!(program_manager.contains_program(program.id()).unwrap());
+        assert_eq!(program_manager.loaded_programs(), vec!["hello.aleo".to_string()]);
         assert_eq!(program_manager.get_program(program.id()).unwrap(), program);
         assert_eq!(program_manager.get_program("hello.aleo").unwrap(), program);
         assert!(program_manager.contains_program("hello.aleo").unwrap());
@@ -190,6 +500,7 @@ mod tests {
         let retrieved_program = program_manager.get_program(program.id()).unwrap();
         assert_eq!(replaced_program, program);
         assert_eq!(retrieved_program, program_2);
+        assert_eq!(program_manager.loaded_programs(), vec!["hello.aleo".to_string()]);
     }
 
     #[test]
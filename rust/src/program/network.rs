@@ -16,25 +16,169 @@
 
 use super::*;
 
-impl<N: Network> ProgramManager<N> {
-    /// Broadcast a transaction to the network
+use snarkvm::synthesizer::Header;
+use std::{thread, time::Duration};
+
+// Number of times the network is polled for a transaction before giving up
+const CONFIRMATION_ATTEMPTS: u32 = 30;
+// Delay between polls for a transaction
+const CONFIRMATION_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A transaction included in a block, returned by [ProgramManager::wait_for_confirmation]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfirmedTransaction<N: Network> {
+    /// The confirmed transaction
+    pub transaction: Transaction<N>,
+    /// The height of the block which includes the transaction
+    pub block_height: u32,
+    /// The number of blocks on top of the block which includes the transaction
+    pub depth: u32,
+}
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Broadcast a transaction to the network.
+    ///
+    /// Broadcasting is idempotent: if the broadcast receives no response but the node already
+    /// has the transaction in its memory pool or in a block, i.e. because the broadcast or an
+    /// earlier one which timed out went through, the broadcast succeeds and returns the
+    /// transaction ID instead of reporting a failure. A transaction rejected by the node or
+    /// before it is sent fails right away.
     pub fn broadcast_transaction(&self, transaction: Transaction<N>) -> Result<String> {
         let transaction_type = if let Transaction::Deploy(..) = &transaction { "Deployment" } else { "Execute" };
+        let transaction_id = transaction.id();
         let api_client = self.api_client()?;
+        self.report_progress(TransactionPhase::Broadcasting);
         let result = api_client.transaction_broadcast(transaction);
-        if result.is_ok() {
-            println!("✅ {} Transaction successfully posted to {}", transaction_type, api_client.base_url());
-        } else {
-            println!("❌ {} Transaction failed to post to {}", transaction_type, api_client.base_url());
+        match &result {
+            Ok(_) => {
+                println!("✅ {} Transaction successfully posted to {}", transaction_type, api_client.broadcast_url())
+            }
+            Err(error) if error.is::<NoResponse>() && self.is_transaction_known(transaction_id) => {
+                println!(
+                    "✅ {} Transaction {transaction_id} was already received by {}",
+                    transaction_type,
+                    api_client.base_url()
+                );
+                return Ok(serde_json::to_string(&transaction_id)?);
+            }
+            Err(_) => println!("❌ {} Transaction failed to post to {}", transaction_type, api_client.broadcast_url()),
         }
         result
     }
 
+    // Determine if the node has a transaction in a block or in its memory pool
+    fn is_transaction_known(&self, transaction_id: N::TransactionID) -> bool {
+        let Ok(api_client) = self.api_client() else { return false };
+        api_client.get_transaction(transaction_id).is_ok()
+            || api_client
+                .get_memory_pool_transactions()
+                .map_or(false, |transactions| transactions.iter().any(|transaction| transaction.id() == transaction_id))
+    }
+
+    /// Get the size in bytes of the JSON body a transaction is broadcast in, which is the size
+    /// the `max_transaction_size` of the network config is checked against
+    pub fn transaction_size(&self, transaction: &Transaction<N>) -> usize {
+        AleoAPIClient::<N, T>::transaction_body(transaction).len()
+    }
+
+    /// Broadcast transactions which depend on each other in order, broadcasting each transaction
+    /// only once the previous one is confirmed, and return the IDs of the confirmed transactions.
+    ///
+    /// The sequence is aborted at the first transaction which fails to broadcast or isn't
+    /// confirmed in time. The error lists the transactions confirmed before the failure, which
+    /// don't need to be broadcast again.
+    pub fn broadcast_sequence(&self, transactions: Vec<Transaction<N>>) -> Result<Vec<N::TransactionID>> {
+        let total = transactions.len();
+        let mut confirmed = Vec::with_capacity(total);
+        for transaction in transactions {
+            let transaction_id = transaction.id();
+            if let Err(error) =
+                self.broadcast_transaction(transaction).and_then(|_| self.wait_for_transaction(transaction_id))
+            {
+                let confirmed_ids = match confirmed.is_empty() {
+                    true => "none".to_string(),
+                    false => {
+                        confirmed.iter().map(|id: &N::TransactionID| id.to_string()).collect::<Vec<_>>().join(", ")
+                    }
+                };
+                bail!(
+                    "❌ Transaction {} of {total} ({transaction_id}) failed, aborting the sequence. Transactions confirmed before the failure: {confirmed_ids}. Error: {error}",
+                    confirmed.len() + 1,
+                );
+            }
+            confirmed.push(transaction_id);
+        }
+        Ok(confirmed)
+    }
+
+    /// Wait until a transaction is included in a block with at least `min_depth` blocks on top of
+    /// it. A `min_depth` of 0 accepts the transaction as soon as it is in any block, while
+    /// transactions moving large amounts can require a deeper block before they are treated as
+    /// final.
+    ///
+    /// The network is polled until the transaction reaches the depth, failing if the transaction
+    /// isn't included in a block in time or if no new block is added for as long.
+    pub fn wait_for_confirmation(
+        &self,
+        transaction_id: N::TransactionID,
+        min_depth: u32,
+    ) -> Result<ConfirmedTransaction<N>> {
+        let transaction = self.wait_for_transaction(transaction_id)?;
+        let api_client = self.api_client()?;
+        let block_height = api_client.get_block_by_hash(api_client.find_block_hash(transaction_id)?)?.height();
+
+        let mut latest_height = api_client.latest_height()?;
+        let mut attempts = 0;
+        loop {
+            let depth = latest_height.saturating_sub(block_height);
+            if depth >= min_depth {
+                return Ok(ConfirmedTransaction { transaction, block_height, depth });
+            }
+            ensure!(
+                attempts < CONFIRMATION_ATTEMPTS,
+                "❌ Transaction {transaction_id} did not reach a depth of {min_depth} blocks, no block was added after height {latest_height}"
+            );
+            println!(
+                "Waiting for transaction {transaction_id} to reach a depth of {min_depth} blocks, current depth: {depth}.."
+            );
+            thread::sleep(CONFIRMATION_INTERVAL);
+
+            // Only give up if the chain stops growing, as deep confirmations take many blocks
+            let height = api_client.latest_height()?;
+            attempts = if height > latest_height { 0 } else { attempts + 1 };
+            latest_height = height;
+        }
+    }
+
+    // Poll the network until a transaction is confirmed
+    pub(crate) fn wait_for_transaction(&self, transaction_id: N::TransactionID) -> Result<Transaction<N>> {
+        let api_client = self.api_client()?;
+        for _ in 0..CONFIRMATION_ATTEMPTS {
+            if let Ok(transaction) = api_client.get_transaction(transaction_id) {
+                println!("✅ Transaction {transaction_id} confirmed");
+                return Ok(transaction);
+            }
+            println!("Waiting for transaction {transaction_id} to be confirmed..");
+            thread::sleep(CONFIRMATION_INTERVAL);
+        }
+        bail!("❌ Transaction {transaction_id} was not confirmed in time")
+    }
+
     /// Get a reference to the configured API client
-    pub fn api_client(&self) -> Result<&AleoAPIClient<N>> {
+    pub fn api_client(&self) -> Result<&AleoAPIClient<N, T>> {
         self.api_client.as_ref().ok_or_else(|| anyhow!("No API client found"))
     }
 
+    /// Replace the API client used to find programs on the Aleo network and to broadcast
+    /// transactions, returning the previously configured client. Passing `None` takes the
+    /// program manager offline.
+    ///
+    /// Programs already added to the program manager are kept, so a program manager started
+    /// offline can be connected to the network later without being rebuilt.
+    pub fn set_api_client(&mut self, api_client: Option<AleoAPIClient<N, T>>) -> Option<AleoAPIClient<N, T>> {
+        std::mem::replace(&mut self.api_client, api_client)
+    }
+
     /// Check the on-chain version of a program to determine if it is deployed, and if so,
     /// if it is the same as the local version
     pub fn on_chain_program_state(&self, program: &Program<N>) -> Result<OnChainProgramState> {
@@ -42,23 +186,75 @@ impl<N: Network> ProgramManager<N> {
         Ok(self
             .api_client()?
             .get_program(program_id)
-            .map(
-                |chain_program| {
-                    if chain_program.eq(program) { OnChainProgramState::Same } else { OnChainProgramState::Different }
-                },
-            )
+            .map(|chain_program| {
+                if self.programs_equivalent(&chain_program, program) {
+                    OnChainProgramState::Same
+                } else {
+                    OnChainProgramState::Different
+                }
+            })
             .unwrap_or(OnChainProgramState::NotDeployed))
     }
+
+    /// Determine if two programs are equivalent, ignoring differences in how they are written.
+    ///
+    /// Programs are compared in a canonical form: printed as parsed, so whitespace and comments
+    /// are ignored, with their imports and declarations sorted, so declaring them in a different
+    /// order is also ignored. The instructions of each closure and function must be the same.
+    pub fn programs_equivalent(&self, a: &Program<N>, b: &Program<N>) -> bool {
+        Self::canonical_form(a) == Self::canonical_form(b)
+    }
+
+    // Split a printed program into its imports and declarations, sorted. snarkVM prints each of
+    // them starting on an unindented line, followed by the indented lines of its body.
+    fn canonical_form(program: &Program<N>) -> Vec<String> {
+        let mut blocks = Vec::<String>::new();
+        for line in program.to_string().lines().filter(|line| !line.trim().is_empty()) {
+            match blocks.last_mut() {
+                Some(block) if line.starts_with(char::is_whitespace) => {
+                    block.push('\n');
+                    block.push_str(line.trim());
+                }
+                _ => blocks.push(line.trim().to_string()),
+            }
+        }
+        blocks.sort();
+        blocks
+    }
+
+    /// Check that a transaction is included in the block with the given header.
+    ///
+    /// The header should come from a trusted source, i.e. a node the caller trusts or headers
+    /// synced and verified earlier. The inclusion proof of the transaction is fetched from the
+    /// configured node and checked against the transactions root of the header, so a node can't
+    /// claim a transaction is confirmed in that block without it being there.
+    pub fn verify_transaction_inclusion(
+        &self,
+        transaction_id: N::TransactionID,
+        block_header: &Header<N>,
+    ) -> Result<bool> {
+        let proof = self.api_client()?.get_transaction_inclusion_proof(transaction_id).map_err(|error| {
+            anyhow!("❌ Failed to get the inclusion proof of transaction {transaction_id}: {error}")
+        })?;
+        ensure!(
+            proof.verify(block_header),
+            "❌ The inclusion proof of transaction {transaction_id} in block {} does not verify against the transactions root {} of the block header",
+            proof.block_hash(),
+            block_header.transactions_root()
+        );
+        Ok(true)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        test_utils::{random_program, GENERIC_PROGRAM_BODY, RECIPIENT_PRIVATE_KEY},
+        test_utils::{random_program, MockTransport, EXECUTE_TRANSACTION, GENERIC_PROGRAM_BODY, RECIPIENT_PRIVATE_KEY},
         AleoAPIClient,
+        HttpMethod,
     };
-    use snarkvm_console::{account::PrivateKey, network::Testnet3};
+    use snarkvm_console::{account::PrivateKey, network::Testnet3, prelude::FromBytes};
 
     use std::{ops::Add, str::FromStr};
 
@@ -99,4 +295,244 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(temp_dir);
     }
+
+    #[test]
+    fn test_programs_equivalent() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let program = |source: &str| Program::<Testnet3>::from_str(source).unwrap();
+        let original = program(
+            "import credits.aleo;
+import token.aleo;
+program app.aleo;
+
+struct pair:
+    a as u32;
+    b as u32;
+
+function sum:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    add r0 r1 into r2;
+    output r2 as u32.private;
+
+function product:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    mul r0 r1 into r2;
+    output r2 as u32.private;
+",
+        );
+
+        // Ensure differences in whitespace, comments and declaration order are ignored
+        let reformatted = program(
+            "import token.aleo;   import credits.aleo;
+program app.aleo;
+// Multiply two numbers
+function product:
+  input r0 as u32.public;  input r1 as u32.private;
+  mul r0 r1 into r2;
+  output r2 as u32.private;
+struct pair: a as u32; b as u32;
+function sum:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    add   r0 r1   into r2;
+    output r2 as u32.private;
+",
+        );
+        assert_ne!(original.to_string(), reformatted.to_string());
+        assert!(program_manager.programs_equivalent(&original, &reformatted));
+
+        // Ensure differences in instructions, declarations and imports are detected
+        let different_instruction = program(&original.to_string().replace("mul r0 r1", "sub r0 r1"));
+        assert!(!program_manager.programs_equivalent(&original, &different_instruction));
+        let different_struct = program(&original.to_string().replace("b as u32", "b as u64"));
+        assert!(!program_manager.programs_equivalent(&original, &different_struct));
+        let missing_import = program(&original.to_string().replace("import token.aleo;", ""));
+        assert!(!program_manager.programs_equivalent(&original, &missing_import));
+
+        // Ensure declarations are compared as a whole, not line by line
+        let canonical_form = ProgramManager::<Testnet3>::canonical_form(&original);
+        assert_eq!(canonical_form.len(), 6);
+        assert!(canonical_form.contains(&"struct pair:\na as u32;\nb as u32;".to_string()));
+    }
+
+    #[test]
+    fn test_broadcast_transaction_is_idempotent() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+        let transaction_id = transaction.id();
+        let base_url = "http://localhost:3030/testnet3";
+        let api_client = |transport| {
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap()
+        };
+        let program_manager =
+            |api_client| ProgramManager::new(Some(private_key), None, Some(api_client), None).unwrap();
+        // No broadcast response is registered, so the broadcast receives no response
+        let known_transaction = || {
+            MockTransport::new().with_response(
+                HttpMethod::Get,
+                &format!("{base_url}/transaction/{transaction_id}"),
+                200,
+                EXECUTE_TRANSACTION,
+            )
+        };
+        let expected_result = serde_json::to_string(&transaction_id).unwrap();
+
+        let memory_pool = |transactions: &str| {
+            MockTransport::new().with_response(
+                HttpMethod::Get,
+                &format!("{base_url}/memoryPool/transactions"),
+                200,
+                transactions,
+            )
+        };
+
+        // Ensure a broadcast without a response of a transaction the node already has in a block
+        // or in its memory pool succeeds
+        let result = program_manager(api_client(known_transaction())).broadcast_transaction(transaction.clone());
+        assert_eq!(result.unwrap(), expected_result);
+        let transport = memory_pool(&format!("[{EXECUTE_TRANSACTION}]"));
+        let result = program_manager(api_client(transport)).broadcast_transaction(transaction.clone());
+        assert_eq!(result.unwrap(), expected_result);
+
+        // Ensure a broadcast without a response of a transaction the node doesn't have still fails
+        let error = program_manager(api_client(memory_pool("[]"))).broadcast_transaction(transaction.clone());
+        assert!(error.unwrap_err().is::<NoResponse>());
+        let error = program_manager(api_client(MockTransport::new())).broadcast_transaction(transaction.clone());
+        assert!(error.unwrap_err().is::<NoResponse>());
+
+        // Ensure a broadcast rejected by the node or before it is sent fails without checking if
+        // the node has the transaction
+        let transport =
+            known_transaction().with_response(HttpMethod::Post, &format!("{base_url}/transaction/broadcast"), 500, "");
+        let error = program_manager(api_client(transport)).broadcast_transaction(transaction.clone()).unwrap_err();
+        assert!(error.to_string().contains("status code 500"));
+        let size_limited = api_client(known_transaction()).with_max_transaction_size(Some(1));
+        let error = program_manager(size_limited).broadcast_transaction(transaction).unwrap_err();
+        assert!(error.to_string().contains("larger than the limit"));
+    }
+
+    #[test]
+    fn test_transaction_size() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+        let transport = MockTransport::new().with_response(
+            HttpMethod::Post,
+            "http://localhost:3030/testnet3/transaction/broadcast",
+            200,
+            &transaction.id().to_string(),
+        );
+        let api_client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+        let program_manager = ProgramManager::new(Some(private_key), None, Some(api_client.clone()), None).unwrap();
+
+        // Ensure the size is the one checked against the transaction size limit when broadcasting
+        let size = program_manager.transaction_size(&transaction);
+        assert_eq!(size, serde_json::to_string(&transaction).unwrap().len());
+        assert!(api_client.with_max_transaction_size(Some(size)).transaction_broadcast(transaction.clone()).is_ok());
+        assert!(api_client.with_max_transaction_size(Some(size - 1)).transaction_broadcast(transaction).is_err());
+    }
+
+    #[test]
+    fn test_broadcast_sequence() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+        let transaction_id = transaction.id();
+        let broadcast_url = "http://localhost:3030/testnet3/transaction/broadcast";
+        let program_manager = |transport| {
+            let api_client =
+                AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+            ProgramManager::new(Some(private_key), None, Some(api_client), None).unwrap()
+        };
+
+        // Ensure confirmed transactions are reported in order
+        let transport = MockTransport::new()
+            .with_response(HttpMethod::Post, broadcast_url, 200, &transaction_id.to_string())
+            .with_response(
+                HttpMethod::Get,
+                &format!("http://localhost:3030/testnet3/transaction/{transaction_id}"),
+                200,
+                EXECUTE_TRANSACTION,
+            );
+        let confirmed = program_manager(transport).broadcast_sequence(vec![transaction.clone()]).unwrap();
+        assert_eq!(confirmed, vec![transaction_id]);
+        assert!(program_manager(MockTransport::new()).broadcast_sequence(vec![]).unwrap().is_empty());
+
+        // Ensure the sequence is aborted when a transaction fails to broadcast
+        let transport = MockTransport::new().with_response(HttpMethod::Post, broadcast_url, 500, "Internal error");
+        let error = program_manager(transport).broadcast_sequence(vec![transaction.clone(), transaction]).unwrap_err();
+        assert!(error.to_string().contains("Transaction 1 of 2"));
+        assert!(error.to_string().contains("confirmed before the failure: none"));
+    }
+
+    #[test]
+    fn test_verify_transaction_inclusion() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let block_bytes = snarkvm::parameters::testnet3::GenesisBytes::load_bytes();
+        let block = Block::<Testnet3>::from_bytes_le(block_bytes).unwrap();
+        let included_id = *block.transaction_ids().next().unwrap();
+        let missing_id = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap().id();
+        let base_url = "http://localhost:3030/testnet3";
+        let block_hash = serde_json::to_string(&block.hash()).unwrap();
+        let transport = MockTransport::new()
+            .with_response(HttpMethod::Get, &format!("{base_url}/find/blockHash/{included_id}"), 200, &block_hash)
+            .with_response(HttpMethod::Get, &format!("{base_url}/find/blockHash/{missing_id}"), 200, &block_hash)
+            .with_response(
+                HttpMethod::Get,
+                &format!("{base_url}/block/{}", block.hash()),
+                200,
+                &serde_json::to_string(&block).unwrap(),
+            );
+        let api_client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+        let program_manager = ProgramManager::new(Some(private_key), None, Some(api_client), None).unwrap();
+
+        // Ensure transactions in the block are verified and a node claiming a transaction is in a
+        // block which doesn't contain it is caught
+        assert!(program_manager.verify_transaction_inclusion(included_id, block.header()).unwrap());
+        let error = program_manager.verify_transaction_inclusion(missing_id, block.header()).unwrap_err();
+        assert!(error.to_string().contains("is not in the block transactions"));
+    }
+
+    #[test]
+    fn test_wait_for_confirmation() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let transaction_id = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap().id();
+        let block_bytes = snarkvm::parameters::testnet3::GenesisBytes::load_bytes();
+        let block = Block::<Testnet3>::from_bytes_le(block_bytes).unwrap();
+        let base_url = "http://localhost:3030/testnet3";
+        let transport = MockTransport::new()
+            .with_response(
+                HttpMethod::Get,
+                &format!("{base_url}/transaction/{transaction_id}"),
+                200,
+                EXECUTE_TRANSACTION,
+            )
+            .with_response(
+                HttpMethod::Get,
+                &format!("{base_url}/find/blockHash/{transaction_id}"),
+                200,
+                &serde_json::to_string(&block.hash()).unwrap(),
+            )
+            .with_response(
+                HttpMethod::Get,
+                &format!("{base_url}/block/{}", block.hash()),
+                200,
+                &serde_json::to_string(&block).unwrap(),
+            )
+            .with_response(HttpMethod::Get, &format!("{base_url}/latest/height"), 200, "5");
+        let api_client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+        let program_manager = ProgramManager::new(Some(private_key), None, Some(api_client), None).unwrap();
+
+        // Ensure the depth of the transaction's block is reported once it is deep enough
+        for min_depth in [0, 5] {
+            let confirmed = program_manager.wait_for_confirmation(transaction_id, min_depth).unwrap();
+            assert_eq!(confirmed.transaction.id(), transaction_id);
+            assert_eq!(confirmed.block_height, 0);
+            assert_eq!(confirmed.depth, 5);
+        }
+    }
 }
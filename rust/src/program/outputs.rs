@@ -0,0 +1,274 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::synthesizer::{Instruction, Operand};
+use snarkvm_console::{
+    program::{Register, Request, ValueType},
+    types::Group,
+};
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Decrypt the records output by a transaction which are owned by the program manager's
+    /// account, in the order the transaction outputs them.
+    ///
+    /// This checks only the given transaction, i.e. to find the records received right after a
+    /// transaction is confirmed, without scanning the ledger for records.
+    pub fn owned_outputs(
+        &self,
+        transaction: &Transaction<N>,
+        password: Option<&str>,
+    ) -> Result<Vec<Record<N, Plaintext<N>>>> {
+        let view_key = ViewKey::try_from(self.get_private_key(password)?)?;
+        let address_x_coordinate = view_key.to_address().to_x_coordinate();
+        transaction
+            .records()
+            .filter(|(_, record)| record.is_owner_with_address_x_coordinate(&view_key, &address_x_coordinate))
+            .map(|(_, record)| record.decrypt(&view_key))
+            .collect()
+    }
+
+    /// Predict the nonce of a record output by a function before the function is executed, so a
+    /// wallet can watch for the record once the transaction is broadcast.
+    ///
+    /// A record cast in a function gets a nonce derived from the transition view key of the
+    /// signed request executing the function and from the register the record is cast into. The
+    /// prediction therefore only holds for a transaction built from this exact request: signing
+    /// the same inputs again samples a new transition view key, which changes every nonce.
+    ///
+    /// Records which are inputs of the function or outputs of a call to another function get
+    /// their nonce elsewhere, so their nonce can't be predicted from the request and an error
+    /// is returned for them.
+    pub fn predict_output_nonce(&self, request: &Request<N>, output_index: usize) -> Result<Group<N>> {
+        let (program_id, function_name) = (request.program_id(), request.function_name());
+        let function = self.load_function(program_id, function_name)?;
+        let output = function.outputs().get_index(output_index).ok_or_else(|| {
+            anyhow!("❌ Function {function_name:?} from program {program_id:?} has no output {output_index}")
+        })?;
+        ensure!(
+            matches!(output.value_type(), ValueType::Record(..) | ValueType::ExternalRecord(..)),
+            "❌ Output {output_index} of function {function_name:?} from program {program_id:?} is not a record"
+        );
+        let locator = match output.operand() {
+            // Registers are assigned once, so the record is cast in the function if a cast
+            // instruction writes its register
+            Operand::Register(register @ Register::Locator(locator))
+                if function.instructions().iter().any(|instruction| {
+                    matches!(instruction, Instruction::Cast(_)) && instruction.destinations().contains(register)
+                }) =>
+            {
+                *locator
+            }
+            _ => bail!(
+                "❌ Output {output_index} of function {function_name:?} from program {program_id:?} is not a record cast in the function"
+            ),
+        };
+        let randomizer = N::hash_to_scalar_psd2(&[*request.tvk(), Field::from_u64(locator)])?;
+        Ok(N::g_scalar_multiply(&randomizer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{EXECUTE_TRANSACTION, RECIPIENT_ADDRESS, RECIPIENT_PRIVATE_KEY};
+
+    use snarkvm::synthesizer::{Authorization, Execution, Output, Transition};
+    use snarkvm_console::{prelude::Uniform, types::Scalar};
+
+    // Rebuild the execution of a transaction so its transition outputs the given records
+    fn transaction_with_outputs(records: Vec<Record<Testnet3, Ciphertext<Testnet3>>>) -> Transaction<Testnet3> {
+        let Transaction::Execute(_, execution, fee) = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap() else {
+            unreachable!("The test transaction is an execution")
+        };
+        let transition = execution.transitions().next().unwrap();
+        let outputs = records
+            .into_iter()
+            .enumerate()
+            .map(|(i, record)| Output::Record(Field::from_u64(i as u64), Field::from_u64(i as u64), Some(record)))
+            .collect();
+        let transition = Transition::new(
+            *transition.program_id(),
+            *transition.function_name(),
+            transition.inputs().to_vec(),
+            outputs,
+            None,
+            transition.proof().clone(),
+            *transition.tpk(),
+            *transition.tcm(),
+        )
+        .unwrap();
+        let execution = Execution::from([transition].into_iter(), execution.global_state_root(), None).unwrap();
+        Transaction::from_execution(execution, fee).unwrap()
+    }
+
+    // Create an encrypted record holding the given amount of microcredits for an address
+    fn encrypted_record(owner: &Address<Testnet3>, microcredits: u64) -> Record<Testnet3, Ciphertext<Testnet3>> {
+        let randomizer = Scalar::<Testnet3>::rand(&mut rand::thread_rng());
+        let nonce = Testnet3::g_scalar_multiply(&randomizer);
+        Record::<Testnet3, Plaintext<Testnet3>>::from_str(&format!(
+            "{{ owner: {owner}.private, microcredits: {microcredits}u64.private, _nonce: {nonce}.public }}"
+        ))
+        .unwrap()
+        .encrypt(randomizer)
+        .unwrap()
+    }
+
+    const TOKEN_PROGRAM: &str = "program token.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function mint:
+    input r0 as u64.private;
+    cast self.caller r0 into r1 as token.record;
+    output r0 as u64.private;
+    output r1 as token.record;
+";
+
+    // Create a program manager for the token program and a request to mint a token
+    fn mint_request() -> (ProgramManager<Testnet3>, Request<Testnet3>) {
+        let rng = &mut rand::thread_rng();
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        program_manager.add_program(&Program::from_str(TOKEN_PROGRAM).unwrap()).unwrap();
+        let request = Request::sign(
+            &private_key,
+            ProgramID::from_str("token.aleo").unwrap(),
+            Identifier::from_str("mint").unwrap(),
+            ["5u64"].into_iter(),
+            &[ValueType::from_str("u64.private").unwrap()],
+            rng,
+        )
+        .unwrap();
+        (program_manager, request)
+    }
+
+    #[test]
+    fn test_predict_output_nonce_rejects_outputs_which_are_not_records() {
+        let (program_manager, request) = mint_request();
+        assert!(program_manager.predict_output_nonce(&request, 1).is_ok());
+        assert!(program_manager.predict_output_nonce(&request, 0).unwrap_err().to_string().contains("not a record"));
+        assert!(program_manager.predict_output_nonce(&request, 2).unwrap_err().to_string().contains("no output 2"));
+    }
+
+    #[test]
+    fn test_predict_output_nonce_rejects_records_from_calls() {
+        let (mut program_manager, _) = mint_request();
+        program_manager
+            .add_program(
+                &Program::from_str(
+                    "import token.aleo;
+
+program wrapper.aleo;
+
+function mint:
+    input r0 as u64.private;
+    call token.aleo/mint r0 into r1 r2;
+    output r2 as token.aleo/token.record;
+",
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        let private_key = program_manager.get_private_key(None).unwrap();
+        let request = Request::sign(
+            &private_key,
+            ProgramID::from_str("wrapper.aleo").unwrap(),
+            Identifier::from_str("mint").unwrap(),
+            ["5u64"].into_iter(),
+            &[ValueType::from_str("u64.private").unwrap()],
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+
+        // Ensure a record output by a call isn't predicted from the request of the caller
+        let error = program_manager.predict_output_nonce(&request, 0).unwrap_err();
+        assert!(error.to_string().contains("not a record cast in the function"));
+    }
+
+    #[test]
+    #[ignore]
+    fn test_predict_output_nonce_matches_transaction() {
+        use snarkvm::synthesizer::{ConsensusMemory, ConsensusStore, VM};
+
+        let rng = &mut rand::thread_rng();
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let request = Request::sign(
+            &private_key,
+            ProgramID::from_str("credits.aleo").unwrap(),
+            Identifier::from_str("mint").unwrap(),
+            [RECIPIENT_ADDRESS, "100u64"].into_iter(),
+            &[ValueType::from_str("address.public").unwrap(), ValueType::from_str("u64.public").unwrap()],
+            rng,
+        )
+        .unwrap();
+        let predicted_nonce = program_manager.predict_output_nonce(&request, 0).unwrap();
+
+        // Ensure the prediction matches the nonce of the record in a transaction built from the
+        // request
+        let vm = VM::from(ConsensusStore::<Testnet3, ConsensusMemory<Testnet3>>::open(None).unwrap()).unwrap();
+        let transaction =
+            Transaction::execute_authorization(&vm, Authorization::new(&[request]), None, None, rng).unwrap();
+        let (_, record) = transaction.records().next().unwrap();
+        assert_eq!(*record.nonce(), predicted_nonce);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_predict_output_nonce() {
+        use snarkvm::{circuit::AleoV0, synthesizer::Process};
+
+        let (program_manager, request) = mint_request();
+        let predicted_nonce = program_manager.predict_output_nonce(&request, 1).unwrap();
+
+        // Ensure the prediction matches the nonce of the record output when the request is evaluated
+        let mut process = Process::<Testnet3>::load().unwrap();
+        process.add_program(&Program::from_str(TOKEN_PROGRAM).unwrap()).unwrap();
+        let response = process.evaluate::<AleoV0>(Authorization::new(&[request])).unwrap();
+        let Value::Record(record) = &response.outputs()[1] else { panic!("The second output is a record") };
+        assert_eq!(*record.nonce(), predicted_nonce);
+    }
+
+    #[test]
+    fn test_owned_outputs() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+        let other_address = Address::try_from(PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap()).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+
+        // Ensure only the records owned by the program manager's account are returned in order,
+        // including the change of the fee, which the test transaction's account paid
+        let transaction = transaction_with_outputs(vec![
+            encrypted_record(&address, 1),
+            encrypted_record(&other_address, 2),
+            encrypted_record(&address, 3),
+        ]);
+        let outputs = program_manager.owned_outputs(&transaction, None).unwrap();
+        let microcredits = outputs.iter().map(|record| record.microcredits().unwrap()).collect::<Vec<_>>();
+        assert_eq!(microcredits, vec![1, 3, 48_200_000]);
+        assert!(outputs.iter().all(|record| **record.owner() == address));
+
+        // Ensure no records are returned for an account which doesn't own any of the outputs
+        let other_program_manager =
+            ProgramManager::<Testnet3>::new(Some(PrivateKey::new(&mut rand::thread_rng()).unwrap()), None, None, None)
+                .unwrap();
+        assert!(other_program_manager.owned_outputs(&transaction, None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use nom::error::{VerboseError, VerboseErrorKind};
+use snarkvm::synthesizer::Process;
+use snarkvm_console::prelude::Parser;
+use std::fmt;
+
+/// A position in the source of a program, counting lines and columns from 1
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourcePosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Error returned by [ProgramManager::parse_program] when a program is invalid
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramParseError {
+    /// The position of the first invalid part of the program, if it is known
+    pub position: Option<SourcePosition>,
+    /// A description of the error
+    pub message: String,
+}
+
+impl fmt::Display for ProgramParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(SourcePosition { line, column }) => {
+                write!(f, "❌ Invalid program at line {line}, column {column}: {}", self.message)
+            }
+            None => write!(f, "❌ Invalid program: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ProgramParseError {}
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Parse a program from its source and check its types without querying the Aleo Network,
+    /// i.e. to lint programs in an editor.
+    ///
+    /// Parsing checks the syntax of the program and that its names are valid and unique. The
+    /// program is then added to a process holding credits.aleo and the given imports, which
+    /// checks the types of its registers and the programs, functions and types it refers to.
+    /// Imports must be given before the programs importing them. If the program is invalid, a
+    /// [ProgramParseError] points at the first part of the program which couldn't be parsed, or
+    /// describes the type error found.
+    ///
+    /// snarkVM downloads the keys of credits.aleo to load the process the first time they are
+    /// needed, see [NetworkConfig::install_parameters].
+    pub fn parse_program(source: &str, imports: &[Program<N>]) -> Result<Program<N>> {
+        let error = match Program::<N>::parse(source) {
+            Ok(("", program)) => return Self::check_program(program, imports),
+            // The program is parsed up to the first component which isn't valid
+            Ok((remainder, _)) => VerboseError { errors: vec![(remainder, VerboseErrorKind::Context("component"))] },
+            Err(nom::Err::Error(error) | nom::Err::Failure(error)) => error,
+            Err(nom::Err::Incomplete(_)) => VerboseError { errors: vec![] },
+        };
+        let error = match error.errors.first() {
+            // Programs whose components can't be added to a program fail after all of them are parsed
+            Some((_, VerboseErrorKind::Nom(nom::error::ErrorKind::MapRes))) | None => ProgramParseError {
+                position: None,
+                message: "a name is declared twice, reserved or refers to an undefined type".to_string(),
+            },
+            Some((remainder, _)) => ProgramParseError {
+                position: Some(Self::source_position(source, remainder)),
+                message: match remainder.lines().next().map(str::trim) {
+                    Some(line) if !line.is_empty() => format!("unexpected `{line}`"),
+                    _ => "unexpected end of program".to_string(),
+                },
+            },
+        };
+        Err(error.into())
+    }
+
+    // Check the types of a parsed program by adding it to a process holding its imports
+    fn check_program(program: Program<N>, imports: &[Program<N>]) -> Result<Program<N>> {
+        let mut process = Process::<N>::load()?;
+        for import in imports {
+            if !process.contains_program(import.id()) {
+                process.add_program(import)?;
+            }
+        }
+        process
+            .add_program(&program)
+            .map_err(|error| ProgramParseError { position: None, message: error.to_string() })?;
+        Ok(program)
+    }
+
+    // Find the position of the remainder of a string in the string
+    fn source_position(source: &str, remainder: &str) -> SourcePosition {
+        let parsed = &source[..source.len() - remainder.len()];
+        let line_start = parsed.rfind('\n').map_or(0, |index| index + 1);
+        SourcePosition { line: parsed.matches('\n').count() + 1, column: parsed[line_start..].chars().count() + 1 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{HELLO_PROGRAM, IMPORT_PROGRAM};
+
+    // Parse a program which should be invalid, returning its parse error
+    fn parse_error(source: &str) -> ProgramParseError {
+        ProgramManager::<Testnet3>::parse_program(source, &[]).unwrap_err().downcast::<ProgramParseError>().unwrap()
+    }
+
+    #[test]
+    fn test_parse_program_syntax_errors() {
+        // Ensure an invalid instruction is reported where it is found
+        let error = parse_error(
+            "program hello.aleo;
+
+function main:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    addd r0 r1 into r2;
+    output r2 as u32.private;
+",
+        );
+        assert_eq!(error.position, Some(SourcePosition { line: 6, column: 5 }));
+        assert!(error.to_string().contains("line 6, column 5: unexpected `addd r0 r1 into r2;`"));
+
+        // Ensure errors in the program header are reported
+        let error = parse_error("program hello;\n\nfunction main:\n    input r0 as u32.public;\n");
+        assert_eq!(error.position.unwrap().line, 1);
+
+        // Ensure programs which declare a function twice are rejected without a position
+        let error = parse_error(&format!("{HELLO_PROGRAM}\nfunction main:\n    input r0 as u32.public;\n"));
+        assert_eq!(error.position, None);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_parse_program() {
+        // Ensure valid programs are parsed
+        let program = ProgramManager::<Testnet3>::parse_program(HELLO_PROGRAM, &[]).unwrap();
+        assert_eq!(program, Program::from_str(HELLO_PROGRAM).unwrap());
+        assert!(ProgramManager::<Testnet3>::parse_program(IMPORT_PROGRAM, &[]).is_ok());
+
+        // Ensure a program which parses but adds registers of different types is rejected
+        let error = parse_error(&HELLO_PROGRAM.replace("input r1 as u32.private;", "input r1 as u64.private;"));
+        assert_eq!(error.position, None);
+
+        // Ensure programs can only import the programs given
+        let import = Program::<Testnet3>::from_str(HELLO_PROGRAM).unwrap();
+        let importing_program = "import hello.aleo;
+program hello_caller.aleo;
+
+function main:
+    input r0 as u32.public;
+    call hello.aleo/main r0 r0 into r1;
+    output r1 as u32.private;
+";
+        assert!(ProgramManager::<Testnet3>::parse_program(importing_program, &[import]).is_ok());
+        assert!(ProgramManager::<Testnet3>::parse_program(importing_program, &[]).is_err());
+    }
+
+    #[test]
+    fn test_source_position() {
+        let source = "first\nsecond line\nthird";
+        let position = |offset| ProgramManager::<Testnet3>::source_position(source, &source[offset..]);
+        assert_eq!(position(0), SourcePosition { line: 1, column: 1 });
+        assert_eq!(position(3), SourcePosition { line: 1, column: 4 });
+        assert_eq!(position(6), SourcePosition { line: 2, column: 1 });
+        assert_eq!(position(13), SourcePosition { line: 2, column: 8 });
+        assert_eq!(position(source.len()), SourcePosition { line: 3, column: 6 });
+    }
+}
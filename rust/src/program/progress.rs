@@ -0,0 +1,94 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::sync::Arc;
+
+/// A phase of building and broadcasting a transaction, reported to the progress callback of a
+/// program manager
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransactionPhase {
+    /// The proving and verifying keys of a program's functions are being synthesized
+    Synthesizing,
+    /// The proofs of the transaction's transitions are being computed
+    Proving,
+    /// The transaction is being verified locally with [ProgramManager::verify_transaction]
+    Verifying,
+    /// The transaction is being sent to the network
+    Broadcasting,
+}
+
+/// Callback receiving the phases of transactions built by a program manager
+pub type ProgressCallback = Arc<dyn Fn(TransactionPhase) + Send + Sync>;
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Set a callback which is called each time a deployment, execution or transfer built by the
+    /// program manager enters a new phase, i.e. to show progress while a transaction is proven.
+    /// Passing `None` removes the callback.
+    ///
+    /// snarkVM doesn't report progress within a phase, so only the start of each phase is
+    /// reported. Phases are skipped when they aren't needed, i.e. executions of credits.aleo
+    /// don't need keys to be synthesized. Transactions are only verified before they are
+    /// broadcast when [ProgramManager::verify_transaction] is called, so verification is
+    /// reported by it rather than while a transaction is built.
+    pub fn set_progress_callback(&mut self, progress_callback: Option<ProgressCallback>) {
+        self.progress_callback = progress_callback;
+    }
+
+    // Report a phase to the progress callback if one is set
+    pub(crate) fn report_progress(&self, phase: TransactionPhase) {
+        if let Some(progress_callback) = &self.progress_callback {
+            progress_callback(phase);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransport, EXECUTE_TRANSACTION, RECIPIENT_PRIVATE_KEY};
+
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_progress_callback() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+        let transport = MockTransport::new().with_response(
+            HttpMethod::Post,
+            "http://localhost:3030/testnet3/transaction/broadcast",
+            200,
+            &transaction.id().to_string(),
+        );
+        let api_client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+        let mut program_manager = ProgramManager::new(Some(private_key), None, Some(api_client), None).unwrap();
+
+        // Ensure nothing is reported without a callback
+        program_manager.broadcast_transaction(transaction.clone()).unwrap();
+
+        // Ensure phases are reported to the callback until it is removed
+        let phases = Arc::new(Mutex::new(vec![]));
+        let reported_phases = phases.clone();
+        program_manager.set_progress_callback(Some(Arc::new(move |phase| reported_phases.lock().unwrap().push(phase))));
+        program_manager.report_progress(TransactionPhase::Synthesizing);
+        program_manager.broadcast_transaction(transaction.clone()).unwrap();
+        program_manager.set_progress_callback(None);
+        program_manager.broadcast_transaction(transaction).unwrap();
+        assert_eq!(*phases.lock().unwrap(), vec![TransactionPhase::Synthesizing, TransactionPhase::Broadcasting]);
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm_console::types::Group;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+// Records reserved by pending operations, keyed by their nonces
+type RecordsByNonce<N> = IndexMap<Group<N>, Record<N, Plaintext<N>>>;
+pub(crate) type ReservedRecords<N> = Arc<Mutex<RecordsByNonce<N>>>;
+
+// Reservation of the records used by a pending operation, which releases them when it is dropped
+#[derive(Debug)]
+pub(crate) struct RecordReservation<N: Network> {
+    reserved_records: ReservedRecords<N>,
+    nonces: Vec<Group<N>>,
+}
+
+impl<N: Network> Drop for RecordReservation<N> {
+    fn drop(&mut self) {
+        let mut reserved_records = lock(&self.reserved_records);
+        self.nonces.iter().for_each(|nonce| {
+            reserved_records.remove(nonce);
+        });
+    }
+}
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Get the records used by pending deployments, executions and transfers.
+    ///
+    /// A record is reserved while an operation of the program manager (or of one of its clones)
+    /// uses it and is released when the operation completes or fails. Operations given a record
+    /// which is already reserved fail immediately, so concurrent operations can't both try to
+    /// spend the same record.
+    pub fn reserved_records(&self) -> Vec<Record<N, Plaintext<N>>> {
+        lock(&self.reserved_records).values().cloned().collect()
+    }
+
+    // Reserve the records used by an operation until the returned reservation is dropped, failing
+    // without reserving any of them if one is already reserved
+    pub(crate) fn reserve_records(&self, records: &[&Record<N, Plaintext<N>>]) -> Result<RecordReservation<N>> {
+        let mut reserved_records = lock(&self.reserved_records);
+        for record in records {
+            ensure!(
+                !reserved_records.contains_key(record.nonce()),
+                "❌ Record with nonce {} is already in use by another pending operation",
+                record.nonce()
+            );
+        }
+        let nonces = records.iter().map(|record| *record.nonce()).collect::<Vec<_>>();
+        records.iter().for_each(|record| {
+            reserved_records.insert(*record.nonce(), (*record).clone());
+        });
+        Ok(RecordReservation { reserved_records: self.reserved_records.clone(), nonces })
+    }
+}
+
+// Lock the reserved records. The records are only changed while the lock is held, so they are
+// still consistent if another thread panicked while holding it.
+fn lock<N: Network>(reserved_records: &ReservedRecords<N>) -> MutexGuard<'_, RecordsByNonce<N>> {
+    reserved_records.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{RECIPIENT_PRIVATE_KEY, RECORD_2000000001_MICROCREDITS, RECORD_5_MICROCREDITS};
+
+    #[test]
+    fn test_record_reservations() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let record = Record::<Testnet3, Plaintext<Testnet3>>::from_str(RECORD_5_MICROCREDITS).unwrap();
+        let other_record = Record::<Testnet3, Plaintext<Testnet3>>::from_str(RECORD_2000000001_MICROCREDITS).unwrap();
+
+        // Ensure records reserved by a clone of the program manager on another thread are listed
+        let (clone, reserved_record) = (program_manager.clone(), record.clone());
+        let reservation =
+            std::thread::spawn(move || clone.reserve_records(&[&reserved_record]).unwrap()).join().unwrap();
+        assert_eq!(program_manager.reserved_records(), vec![record.clone()]);
+
+        // Ensure a reserved record can't be reserved again and nothing is reserved by the failure
+        let error = program_manager.reserve_records(&[&other_record, &record]).unwrap_err();
+        assert!(error.to_string().contains("already in use"));
+        assert_eq!(program_manager.reserved_records().len(), 1);
+
+        // Ensure operations given a reserved record fail before using it
+        let recipient = Address::try_from(&private_key).unwrap();
+        let fee = CreditAmount::from_microcredits(1);
+        let error =
+            program_manager.transfer(1, fee, recipient, None, other_record.clone(), record.clone()).unwrap_err();
+        assert!(error.to_string().contains("already in use"));
+        assert_eq!(program_manager.reserved_records(), vec![record.clone()]);
+
+        // Ensure records are released once the reservation is dropped
+        drop(reservation);
+        assert!(program_manager.reserved_records().is_empty());
+        let _reservation = program_manager.reserve_records(&[&record, &other_record]).unwrap();
+        assert_eq!(program_manager.reserved_records().len(), 2);
+    }
+}
@@ -16,7 +16,13 @@
 
 use super::*;
 
-impl<N: Network> ProgramManager<N> {
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Replace the local directory programs are loaded from, returning the previously configured
+    /// directory. Passing `None` disables loading programs from disk.
+    pub fn set_local_program_directory(&mut self, local_program_directory: Option<PathBuf>) -> Option<PathBuf> {
+        std::mem::replace(&mut self.local_program_directory, local_program_directory)
+    }
+
     /// Find a program by first looking on disk, and if not found, on the aleo network
     pub fn find_program(&self, program_id: &ProgramID<N>) -> Result<Program<N>> {
         self.find_program_on_disk(program_id).or_else(|_| self.find_program_on_chain(program_id))
@@ -92,11 +98,13 @@ mod tests {
             random_program_id,
             setup_directory,
             teardown_directory,
+            MockTransport,
             HELLO_PROGRAM,
             IMPORT_PROGRAM,
             RECIPIENT_PRIVATE_KEY,
         },
         AleoAPIClient,
+        HttpMethod,
     };
     use snarkvm_console::{account::PrivateKey, network::Testnet3};
 
@@ -151,6 +159,32 @@ mod tests {
         result.unwrap();
     }
 
+    #[test]
+    fn test_resolution_sources_can_be_replaced() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let test_path = setup_directory("aleo_test_replace_resolution", HELLO_PROGRAM, vec![]).unwrap();
+
+        let result = catch_unwind(|| {
+            // Create a program manager with no way of finding programs
+            let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+            let program_id = ProgramID::<Testnet3>::from_str("hello.aleo").unwrap();
+            assert!(program_manager.find_program(&program_id).is_err());
+
+            // Ensure the program can be found once a local directory is configured
+            assert!(program_manager.set_local_program_directory(Some(test_path.clone())).is_none());
+            let expected_program = Program::<Testnet3>::from_str(HELLO_PROGRAM).unwrap();
+            assert_eq!(program_manager.find_program(&program_id).unwrap(), expected_program);
+
+            // Ensure the previous sources are returned when they are replaced
+            assert!(program_manager.set_api_client(Some(AleoAPIClient::testnet3())).is_none());
+            assert!(program_manager.set_api_client(None).is_some());
+            assert_eq!(program_manager.set_local_program_directory(None), Some(test_path.clone()));
+            assert!(program_manager.find_program_on_disk(&program_id).is_err());
+        });
+        teardown_directory(&test_path);
+        result.unwrap();
+    }
+
     #[test]
     fn test_hybrid_program_and_import_loading() {
         let credits_program_string = Program::<Testnet3>::credits().unwrap().to_string();
@@ -270,4 +304,24 @@ mod tests {
         let imports = program_manager.find_program_imports(&bad_import_program);
         assert!(imports.is_err());
     }
+
+    #[test]
+    fn test_network_resolution_with_mock_transport() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let transport = MockTransport::new().with_response(
+            HttpMethod::Get,
+            "http://localhost:3030/testnet3/program/hello.aleo",
+            200,
+            &serde_json::to_string(HELLO_PROGRAM).unwrap(),
+        );
+        let api_client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap();
+        let program_manager = ProgramManager::new(Some(private_key), None, Some(api_client), None).unwrap();
+
+        // Ensure programs are resolved from the responses of the transport
+        let program_id = ProgramID::<Testnet3>::from_str("hello.aleo").unwrap();
+        let program = program_manager.find_program(&program_id).unwrap();
+        assert_eq!(program, Program::from_str(HELLO_PROGRAM).unwrap());
+        assert!(program_manager.find_program(&ProgramID::from_str("missing.aleo").unwrap()).is_err());
+    }
 }
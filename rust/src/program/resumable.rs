@@ -0,0 +1,294 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use rand::{CryptoRng, Rng};
+use snarkvm::{
+    circuit::AleoV0,
+    synthesizer::{Certificate, Deployment, Process, VerifyingKey},
+};
+use snarkvm_console::prelude::FromBytes;
+use std::{any::Any, fs, path::Path};
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Deploy a program to the network, checkpointing the work done for each of its functions so
+    /// a deployment which fails part way through can be resumed.
+    ///
+    /// Synthesizing the keys of a program with many functions takes a long time. The verifying
+    /// key and certificate of each function are written to `checkpoint_dir` as soon as they are
+    /// synthesized and calling this method again with the same directory skips the functions
+    /// which already have a checkpoint. The checkpoints are removed once the deployment has been
+    /// broadcast.
+    pub fn deploy_resumable(
+        &mut self,
+        program_id: impl TryInto<ProgramID<N>>,
+        checkpoint_dir: &Path,
+        fee: CreditAmount,
+        fee_record: Record<N, Plaintext<N>>,
+        password: Option<&str>,
+    ) -> Result<DeploymentResult<N>> {
+        let fee = fee.microcredits();
+        let _reservation = self.reserve_records(&[&fee_record])?;
+
+        // Check the deployment can be made and load the program and its imports
+        let (program, imports) = self.prepare_deployment(program_id, fee, &fee_record)?;
+
+        // Try to get the private key
+        let private_key = self.get_private_key(password)?;
+
+        // Attempt to construct the transaction, resuming from any existing checkpoints
+        println!("Building transaction..");
+        let query = self.api_client.as_ref().unwrap().base_url();
+        let transaction = Self::build_resumable_deploy_transaction(
+            &program,
+            &imports,
+            &private_key,
+            fee,
+            fee_record,
+            query.to_string(),
+            checkpoint_dir,
+            &|phase| self.report_progress(phase),
+        )?;
+
+        let result = self.broadcast_deployment(program.id(), transaction, fee)?;
+
+        // The deployment was broadcast, so failing to remove its checkpoints isn't an error
+        let program_checkpoint_dir = checkpoint_dir.join(program.id().to_string());
+        if let Err(error) = fs::remove_dir_all(&program_checkpoint_dir) {
+            println!("Failed to remove the checkpoints in {program_checkpoint_dir:?}: {error}");
+        }
+        Ok(result)
+    }
+
+    /// Create a deploy transaction for a program without instantiating the program manager,
+    /// resuming from the function checkpoints found in `checkpoint_dir`
+    pub fn create_resumable_deploy_transaction(
+        program: &Program<N>,
+        imports: &[Program<N>],
+        private_key: &PrivateKey<N>,
+        fee: u64,
+        fee_record: Record<N, Plaintext<N>>,
+        query: String,
+        checkpoint_dir: &Path,
+    ) -> Result<Transaction<N>> {
+        Self::build_resumable_deploy_transaction(
+            program,
+            imports,
+            private_key,
+            fee,
+            fee_record,
+            query,
+            checkpoint_dir,
+            &|_| (),
+        )
+    }
+
+    // Create a resumable deploy transaction, reporting the phases of the deployment
+    #[allow(clippy::too_many_arguments)]
+    fn build_resumable_deploy_transaction(
+        program: &Program<N>,
+        imports: &[Program<N>],
+        private_key: &PrivateKey<N>,
+        fee: u64,
+        fee_record: Record<N, Plaintext<N>>,
+        query: String,
+        checkpoint_dir: &Path,
+        progress: &dyn Fn(TransactionPhase),
+    ) -> Result<Transaction<N>> {
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+        let query = Query::from(query);
+
+        // Attempt to add the programs to a local VM. This will fail if any imports are duplicated.
+        let store = ConsensusStore::<N, ConsensusMemory<N>>::open(None)?;
+        let vm = VM::<N, ConsensusMemory<N>>::from(store)?;
+        imports.iter().try_for_each(|imported_program| {
+            if imported_program.id().to_string() != "credits.aleo" {
+                vm.process().write().add_program(imported_program)?;
+            };
+            Ok::<_, Error>(())
+        })?;
+
+        // Synthesize the deployment and pay for it
+        progress(TransactionPhase::Synthesizing);
+        let deployment = Self::synthesize_deployment(&vm.process().read(), program, checkpoint_dir, rng)?;
+        progress(TransactionPhase::Proving);
+        let (_, fee, _) = vm.execute_fee(private_key, fee_record, fee, Some(query), rng)?;
+        Self::sign_deployment(private_key, deployment, fee, rng)
+    }
+
+    // Build the deployment of a program, loading the verifying key and certificate of each
+    // function from its checkpoint if it has one and checkpointing the functions synthesized
+    fn synthesize_deployment<R: Rng + CryptoRng>(
+        process: &Process<N>,
+        program: &Program<N>,
+        checkpoint_dir: &Path,
+        rng: &mut R,
+    ) -> Result<Deployment<N>> {
+        // Discard checkpoints made for a different version of the program. Checkpoints which
+        // can't be removed are ignored and overwritten as the functions are synthesized again.
+        let program_checkpoint_dir = checkpoint_dir.join(program.id().to_string());
+        let program_checkpoint = program_checkpoint_dir.join("program.aleo");
+        let mut resume = true;
+        if fs::read_to_string(&program_checkpoint).map_or(false, |checkpoint| checkpoint != program.to_string()) {
+            println!("Program {:?} changed since it was checkpointed, discarding checkpoints", program.id());
+            if let Err(error) = fs::remove_dir_all(&program_checkpoint_dir) {
+                println!("Failed to remove the checkpoints in {program_checkpoint_dir:?}: {error}");
+                resume = false;
+            }
+        }
+        fs::create_dir_all(&program_checkpoint_dir)?;
+        fs::write(&program_checkpoint, program.to_string())?;
+
+        // Synthesize the keys in a copy of the process so the process is left unchanged
+        let mut process = process.clone();
+        process.add_program(program)?;
+
+        let mut verifying_keys = Vec::with_capacity(program.functions().len());
+        for function_name in program.functions().keys() {
+            let checkpoint = program_checkpoint_dir.join(format!("{function_name}.checkpoint"));
+            let keys = if resume && checkpoint.exists() {
+                println!("Resuming from checkpoint for function {function_name:?}");
+                let bytes = fs::read(&checkpoint)?;
+                let mut reader = bytes.as_slice();
+                (VerifyingKey::read_le(&mut reader)?, Certificate::read_le(&mut reader)?)
+            } else {
+                println!("Synthesizing keys for function {function_name:?}..");
+                let (verifying_key, certificate) =
+                    Self::synthesize_function_keys(&process, program.id(), function_name, rng)?;
+                // Write the checkpoint to a temporary file first, so an interruption can't
+                // leave a partially written checkpoint behind
+                let mut bytes = verifying_key.to_bytes_le()?;
+                bytes.extend(certificate.to_bytes_le()?);
+                let partial_checkpoint = checkpoint.with_extension("partial");
+                fs::write(&partial_checkpoint, bytes)?;
+                fs::rename(&partial_checkpoint, &checkpoint)?;
+                (verifying_key, certificate)
+            };
+            verifying_keys.push((*function_name, keys));
+        }
+
+        Deployment::new(N::EDITION, program.clone(), verifying_keys)
+    }
+
+    // Synthesize the verifying key and certificate of a function with the circuit of the
+    // process's network
+    fn synthesize_function_keys<R: Rng + CryptoRng>(
+        process: &Process<N>,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        rng: &mut R,
+    ) -> Result<(VerifyingKey<N>, Certificate<N>)> {
+        if let Some(process) = (process as &dyn Any).downcast_ref::<Process<Testnet3>>() {
+            let program_id = cast::<_, ProgramID<Testnet3>>(*program_id)?;
+            let function_name = cast::<_, Identifier<Testnet3>>(*function_name)?;
+            process.synthesize_key::<AleoV0, _>(&program_id, &function_name, rng)?;
+            let proving_key = process.get_proving_key(program_id, function_name)?;
+            let verifying_key = process.get_verifying_key(program_id, function_name)?;
+            let certificate = Certificate::certify(&function_name, &proving_key, &verifying_key)?;
+            return cast((verifying_key, certificate));
+        }
+        bail!("❌ Key synthesis is not supported for network {}", N::ID)
+    }
+}
+
+// Convert between a generic type and its concrete type once the network has been identified
+fn cast<T: 'static, U: 'static>(value: T) -> Result<U> {
+    (Box::new(value) as Box<dyn Any>)
+        .downcast::<U>()
+        .map(|value| *value)
+        .map_err(|_| anyhow!("❌ Type mismatch between network types"))
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "wasm"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        random_program_id,
+        teardown_directory,
+        HELLO_PROGRAM,
+        RECIPIENT_PRIVATE_KEY,
+        RECORD_5_MICROCREDITS,
+    };
+
+    #[test]
+    fn test_deploy_resumable_requires_a_network_client() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        program_manager.add_program(&Program::from_str(HELLO_PROGRAM).unwrap()).unwrap();
+        let fee_record = Record::<Testnet3, Plaintext<Testnet3>>::from_str(RECORD_5_MICROCREDITS).unwrap();
+        let checkpoint_dir = std::env::temp_dir().join("aleo_test_deploy_resumable_requires_a_network_client");
+
+        assert!(
+            program_manager
+                .deploy_resumable("hello.aleo", &checkpoint_dir, CreditAmount::from_microcredits(5), fee_record, None)
+                .is_err()
+        );
+        assert!(!checkpoint_dir.exists());
+    }
+
+    #[test]
+    #[ignore]
+    fn test_synthesize_deployment_resumes_from_checkpoints() {
+        let rng = &mut rand::thread_rng();
+        let program_id = random_program_id(12);
+        let program = Program::<Testnet3>::from_str(&format!(
+            "program {program_id};
+
+function first:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    add r0 r1 into r2;
+    output r2 as u32.private;
+
+function second:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    mul r0 r1 into r2;
+    output r2 as u32.private;
+"
+        ))
+        .unwrap();
+        let checkpoint_dir = std::env::temp_dir().join(format!("aleo_test_checkpoints_{program_id}"));
+        let program_checkpoint_dir = checkpoint_dir.join(&program_id);
+        let process = Process::<Testnet3>::load().unwrap();
+
+        // Synthesize the deployment, checkpointing both functions
+        let deployment =
+            ProgramManager::<Testnet3>::synthesize_deployment(&process, &program, &checkpoint_dir, rng).unwrap();
+        let first_checkpoint = program_checkpoint_dir.join("first.checkpoint");
+        let second_checkpoint = program_checkpoint_dir.join("second.checkpoint");
+        assert!(first_checkpoint.exists() && second_checkpoint.exists());
+        assert!(!process.contains_program(program.id()));
+
+        // Simulate an interruption before the second function was checkpointed, then resume
+        fs::remove_file(&second_checkpoint).unwrap();
+        let first_modified = fs::metadata(&first_checkpoint).unwrap().modified().unwrap();
+        let resumed_deployment =
+            ProgramManager::<Testnet3>::synthesize_deployment(&process, &program, &checkpoint_dir, rng).unwrap();
+
+        // Ensure the first function was not synthesized again and the deployment is complete
+        assert_eq!(fs::metadata(&first_checkpoint).unwrap().modified().unwrap(), first_modified);
+        assert!(second_checkpoint.exists());
+        assert_eq!(resumed_deployment.verifying_keys()[0], deployment.verifying_keys()[0]);
+        assert_eq!(resumed_deployment.verifying_keys().len(), 2);
+        process.verify_deployment::<AleoV0, _>(&resumed_deployment, rng).unwrap();
+
+        teardown_directory(&checkpoint_dir);
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use serde_json::{json, Value as JsonValue};
+use snarkvm_console::program::ValueType;
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Export a JSON description of the functions of a program, their inputs (register name,
+    /// type and visibility) and outputs. The program is taken from the program manager if it has
+    /// been added, otherwise it is searched for on disk and then on the Aleo network.
+    ///
+    /// Example output for `hello.aleo`:
+    /// ```json
+    /// {
+    ///   "program_id": "hello.aleo",
+    ///   "functions": [
+    ///     {
+    ///       "name": "main",
+    ///       "inputs": [
+    ///         { "name": "r0", "type": "u32", "visibility": "public" },
+    ///         { "name": "r1", "type": "u32", "visibility": "private" }
+    ///       ],
+    ///       "outputs": [{ "name": "r2", "type": "u32", "visibility": "private" }]
+    ///     }
+    ///   ]
+    /// }
+    /// ```
+    pub fn export_program_schema(&self, program_id: impl TryInto<ProgramID<N>>) -> Result<String> {
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        let program = self.get_program(program_id).or_else(|_| self.find_program(&program_id))?;
+
+        let functions = program
+            .functions()
+            .values()
+            .map(|function| {
+                let inputs = function
+                    .inputs()
+                    .iter()
+                    .map(|input| Self::value_type_schema(input.register().to_string(), input.value_type()))
+                    .collect::<Vec<_>>();
+                let outputs = function
+                    .outputs()
+                    .iter()
+                    .map(|output| Self::value_type_schema(output.operand().to_string(), output.value_type()))
+                    .collect::<Vec<_>>();
+                json!({ "name": function.name().to_string(), "inputs": inputs, "outputs": outputs })
+            })
+            .collect::<Vec<_>>();
+
+        let schema = json!({ "program_id": program_id.to_string(), "functions": functions });
+        Ok(serde_json::to_string_pretty(&schema)?)
+    }
+
+    // Describe a function input or output by its name, type and visibility. Records carry the
+    // visibility of each of their entries, so they are given the visibility "record".
+    fn value_type_schema(name: String, value_type: &ValueType<N>) -> JsonValue {
+        let (value_type, visibility) = match value_type {
+            ValueType::Constant(plaintext_type) => (plaintext_type.to_string(), "constant"),
+            ValueType::Public(plaintext_type) => (plaintext_type.to_string(), "public"),
+            ValueType::Private(plaintext_type) => (plaintext_type.to_string(), "private"),
+            ValueType::Record(identifier) => (format!("{identifier}.record"), "record"),
+            ValueType::ExternalRecord(locator) => (format!("{locator}.record"), "record"),
+        };
+        json!({ "name": name, "type": value_type, "visibility": visibility })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{HELLO_PROGRAM, RECIPIENT_PRIVATE_KEY};
+    use snarkvm_console::network::Testnet3;
+
+    #[test]
+    fn test_export_program_schema() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        program_manager.add_program(&Program::<Testnet3>::from_str(HELLO_PROGRAM).unwrap()).unwrap();
+
+        let schema: JsonValue =
+            serde_json::from_str(&program_manager.export_program_schema("hello.aleo").unwrap()).unwrap();
+        assert_eq!(schema["program_id"], "hello.aleo");
+
+        let main = &schema["functions"][0];
+        assert_eq!(main["name"], "main");
+        assert_eq!(main["inputs"][0], json!({ "name": "r0", "type": "u32", "visibility": "public" }));
+        assert_eq!(main["inputs"][1], json!({ "name": "r1", "type": "u32", "visibility": "private" }));
+        assert_eq!(main["outputs"][0], json!({ "name": "r2", "type": "u32", "visibility": "private" }));
+
+        // Ensure records are described by their record type
+        program_manager.add_program(&Program::<Testnet3>::credits().unwrap()).unwrap();
+        let schema: JsonValue =
+            serde_json::from_str(&program_manager.export_program_schema("credits.aleo").unwrap()).unwrap();
+        let transfer = schema["functions"].as_array().unwrap().iter().find(|f| f["name"] == "transfer").unwrap();
+        assert_eq!(transfer["inputs"][0], json!({ "name": "r0", "type": "credits.record", "visibility": "record" }));
+
+        // Ensure unknown programs produce an error when no resolution source is configured
+        assert!(program_manager.export_program_schema("unknown.aleo").is_err());
+    }
+}
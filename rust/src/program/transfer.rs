@@ -16,20 +16,198 @@
 
 use super::*;
 
-impl<N: Network> ProgramManager<N> {
-    /// Executes a transfer to the specified recipient_address with the specified amount and fee.
-    /// Specify 0 for no fee.
-    pub fn transfer(
+/// A record which a transfer will create
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedRecord<N: Network> {
+    /// The address which will own the record
+    pub owner: Address<N>,
+    /// The amount of microcredits the record will hold
+    pub microcredits: u64,
+}
+
+/// The records consumed and created by a transfer, found with [ProgramManager::plan_transfer]
+///
+/// The plan is made before the transfer is signed, so it holds no nonces. The nonce of a created
+/// record is derived from the transition view key of the signed request and the register the
+/// record is cast into, so it can be found with [ProgramManager::predict_output_nonce] once the
+/// request has been signed, before the transfer is proven.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransferPlan<N: Network> {
+    /// The record the transferred amount is taken from
+    pub input_record: Record<N, Plaintext<N>>,
+    /// The record the fee is paid from
+    pub fee_record: Record<N, Plaintext<N>>,
+    /// The record created for the recipient
+    pub recipient_record: PlannedRecord<N>,
+    /// The record created for the sender with the rest of the input record
+    pub change_record: PlannedRecord<N>,
+    /// The record created for the sender with the rest of the fee record
+    pub fee_change_record: PlannedRecord<N>,
+    /// The fee paid in microcredits
+    pub fee: u64,
+}
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Plan a transfer without proving or broadcasting it, listing the records it will consume
+    /// and create. This runs the same checks as [ProgramManager::transfer], which fails in the
+    /// same cases, including when the recipient is an unknown contact label.
+    pub fn plan_transfer(
         &self,
         amount: u64,
-        fee: u64,
-        recipient_address: Address<N>,
+        fee: CreditAmount,
+        recipient: impl Into<Recipient<N>>,
         password: Option<&str>,
         input_record: Record<N, Plaintext<N>>,
         fee_record: Record<N, Plaintext<N>>,
-    ) -> Result<String> {
+    ) -> Result<TransferPlan<N>> {
+        let recipient_address = self.resolve_recipient(recipient)?;
         ensure!(amount > 0, "Amount must be greater than 0");
+        let fee = fee.microcredits();
+        ensure!(fee > 0, "Fee must be greater than 0");
+        ensure!(input_record != fee_record, "The same record can't pay for both the transfer and the fee");
+
+        // Records can only be spent by their owner, who also receives the change of the transfer
+        let sender = Address::try_from(self.get_private_key(password)?)?;
+        ensure!(**input_record.owner() == sender, "The input record is not owned by the sender");
+        ensure!(**fee_record.owner() == sender, "The fee record is not owned by the sender");
+
+        InsufficientFunds::check(&[&input_record, &fee_record], amount.saturating_add(fee))?;
+        InsufficientFunds::check(&[&input_record], amount)?;
+        InsufficientFunds::check(&[&fee_record], fee)?;
+        let (input_microcredits, fee_microcredits) = (input_record.microcredits()?, fee_record.microcredits()?);
+
+        Ok(TransferPlan {
+            input_record,
+            fee_record,
+            recipient_record: PlannedRecord { owner: recipient_address, microcredits: amount },
+            change_record: PlannedRecord { owner: sender, microcredits: input_microcredits - amount },
+            fee_change_record: PlannedRecord { owner: sender, microcredits: fee_microcredits - fee },
+            fee,
+        })
+    }
+
+    /// Update a transfer plan with the fee actually charged for the transfer, i.e. once it has
+    /// been estimated, so the change of its records matches the executed transfer.
+    ///
+    /// The planned records are kept if they still cover the amount and the new fee. Otherwise
+    /// records are selected again among the planned records and the given records owned by the
+    /// sender, preferring to keep the planned records and then the records holding the least
+    /// microcredits. Records reserved by pending operations are not selected. Fails with
+    /// [InsufficientFunds] if no two records cover the amount and the new fee.
+    pub fn finalize_transfer_plan(
+        &self,
+        plan: TransferPlan<N>,
+        actual_fee: CreditAmount,
+        records: &[Record<N, Plaintext<N>>],
+    ) -> Result<TransferPlan<N>> {
+        let fee = actual_fee.microcredits();
         ensure!(fee > 0, "Fee must be greater than 0");
+        let amount = plan.recipient_record.microcredits;
+        let sender = plan.change_record.owner;
+
+        // Transfers and fees each spend a single record, so a record covering the amount and
+        // another one covering the fee are needed
+        let reserved_records = self.reserved_records();
+        let mut candidates = vec![plan.input_record.clone(), plan.fee_record.clone()];
+        for record in records {
+            if **record.owner() == sender && !candidates.contains(record) && !reserved_records.contains(record) {
+                candidates.push(record.clone());
+            }
+        }
+        let candidates = candidates
+            .into_iter()
+            .filter_map(|record| record.microcredits().ok().map(|microcredits| (microcredits, record)))
+            .collect::<Vec<_>>();
+        let selection = candidates
+            .iter()
+            .filter(|(input_microcredits, _)| *input_microcredits >= amount)
+            .flat_map(|input| {
+                candidates
+                    .iter()
+                    .filter(move |(fee_microcredits, fee_record)| *fee_microcredits >= fee && *fee_record != input.1)
+                    .map(move |fee| (input, fee))
+            })
+            .min_by_key(|((input_microcredits, input_record), (fee_microcredits, fee_record))| {
+                let total = u128::from(*input_microcredits) + u128::from(*fee_microcredits);
+                (*input_record != plan.input_record, *fee_record != plan.fee_record, total)
+            });
+        let Some(((input_microcredits, input_record), (fee_microcredits, fee_record))) = selection else {
+            let mut holdings = candidates.iter().map(|(microcredits, _)| *microcredits).collect::<Vec<_>>();
+            holdings.sort_unstable();
+            let error = match holdings.iter().position(|microcredits| *microcredits >= amount) {
+                None => InsufficientFunds { required: amount, available: holdings.last().copied().unwrap_or(0) },
+                // The smallest record covering the amount pays it, so the largest other record
+                // falls short of the fee
+                Some(index) => {
+                    holdings.remove(index);
+                    InsufficientFunds { required: fee, available: holdings.last().copied().unwrap_or(0) }
+                }
+            };
+            return Err(error.into());
+        };
+
+        Ok(TransferPlan {
+            input_record: input_record.clone(),
+            fee_record: fee_record.clone(),
+            recipient_record: plan.recipient_record,
+            change_record: PlannedRecord { owner: sender, microcredits: input_microcredits - amount },
+            fee_change_record: PlannedRecord { owner: sender, microcredits: fee_microcredits - fee },
+            fee,
+        })
+    }
+
+    /// Select records holding at least the given amount of microcredits in total, keeping the
+    /// number of records and the amount left over small.
+    ///
+    /// Records are picked from the largest down until one record covers the rest of the amount,
+    /// at which point the smallest record covering it is picked. A single record is returned if
+    /// one holds the whole amount, so transfers and fees, which each spend a single record, can
+    /// use it directly. Records which don't hold credits are ignored. Fails with
+    /// [InsufficientFunds] if the records hold less than the amount in total.
+    pub fn select_records_for_amount(
+        &self,
+        records: &[Record<N, Plaintext<N>>],
+        amount: u64,
+    ) -> Result<Vec<Record<N, Plaintext<N>>>> {
+        ensure!(amount > 0, "Amount must be greater than 0");
+        let mut candidates = records
+            .iter()
+            .filter_map(|record| record.microcredits().ok().map(|microcredits| (microcredits, record)))
+            .collect::<Vec<_>>();
+        let total = candidates.iter().map(|(microcredits, _)| u128::from(*microcredits)).sum::<u128>();
+        if total < u128::from(amount) {
+            return Err(InsufficientFunds { required: amount, available: u64::try_from(total)? }.into());
+        }
+
+        // Sort the records from the largest to the smallest
+        candidates.sort_by(|(first, _), (second, _)| second.cmp(first));
+        let mut selected = vec![];
+        let mut remaining = amount;
+        while remaining > 0 {
+            // Take the smallest record covering the rest of the amount, or the largest record if none does
+            let index = candidates.iter().rposition(|(microcredits, _)| *microcredits >= remaining).unwrap_or(0);
+            let (microcredits, record) = candidates.remove(index);
+            remaining = remaining.saturating_sub(microcredits);
+            selected.push(record.clone());
+        }
+        Ok(selected)
+    }
+
+    /// Executes a transfer of the specified amount of microcredits to the specified recipient,
+    /// paying the specified fee. The recipient can be an address or the label of a contact added
+    /// with [ProgramManager::add_contact].
+    pub fn transfer(
+        &self,
+        amount: u64,
+        fee: CreditAmount,
+        recipient: impl Into<Recipient<N>>,
+        password: Option<&str>,
+        input_record: Record<N, Plaintext<N>>,
+        fee_record: Record<N, Plaintext<N>>,
+    ) -> Result<String> {
+        let _reservation = self.reserve_records(&[&input_record, &fee_record])?;
+        let TransferPlan { input_record, fee_record, recipient_record, fee, .. } =
+            self.plan_transfer(amount, fee, recipient, password, input_record, fee_record)?;
 
         // Specify the network state query
         let query = Query::from(self.api_client.as_ref().unwrap().base_url());
@@ -48,11 +226,12 @@ impl<N: Network> ProgramManager<N> {
             // Prepare the inputs for a transfer.
             let inputs = vec![
                 Value::Record(input_record),
-                Value::from_str(&recipient_address.to_string())?,
+                Value::from_str(&recipient_record.owner.to_string())?,
                 Value::from_str(&format!("{}u64", amount))?,
             ];
 
             // Create a new transaction.
+            self.report_progress(TransactionPhase::Proving);
             Transaction::execute(
                 &vm,
                 &private_key,
@@ -72,10 +251,167 @@ impl<N: Network> ProgramManager<N> {
 mod tests {
     use super::*;
     use crate::{test_utils::BEACON_PRIVATE_KEY, AleoAPIClient, RecordFinder};
-    use snarkvm_console::network::Testnet3;
+    use snarkvm_console::{network::Testnet3, prelude::Uniform};
 
     use std::{str::FromStr, thread};
 
+    // Create a record holding the given amount of microcredits for an address
+    fn record(owner: &Address<Testnet3>, microcredits: u64) -> Record<Testnet3, Plaintext<Testnet3>> {
+        let nonce = Testnet3::g_scalar_multiply(&Uniform::rand(&mut rand::thread_rng()));
+        Record::from_str(&format!(
+            "{{ owner: {owner}.private, microcredits: {microcredits}u64.private, _nonce: {nonce}.public }}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_select_records_for_amount() {
+        let private_key = PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let records = [5, 20, 8, 3].map(|microcredits| record(&address, microcredits));
+        let select = |amount| {
+            program_manager
+                .select_records_for_amount(&records, amount)
+                .map(|records| records.iter().map(|record| record.microcredits().unwrap()).collect::<Vec<_>>())
+        };
+
+        // Ensure the smallest single record covering the amount is preferred
+        assert_eq!(select(7).unwrap(), vec![8]);
+        assert_eq!(select(20).unwrap(), vec![20]);
+        assert_eq!(select(1).unwrap(), vec![3]);
+
+        // Ensure the largest records are combined until a single record covers the rest
+        assert_eq!(select(26).unwrap(), vec![20, 8]);
+        assert_eq!(select(36).unwrap(), vec![20, 8, 5, 3]);
+
+        // Ensure the total available is reported when the records can't cover the amount
+        let error = select(37).unwrap_err();
+        assert_eq!(error.downcast::<InsufficientFunds>().unwrap(), InsufficientFunds { required: 37, available: 36 });
+        assert!(select(0).is_err());
+        assert!(program_manager.select_records_for_amount(&[], 1).is_err());
+    }
+
+    #[test]
+    fn test_plan_transfer() {
+        let private_key = PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap();
+        let sender = Address::try_from(&private_key).unwrap();
+        let recipient = Address::try_from(PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap()).unwrap();
+        let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let (input_record, fee_record) = (record(&sender, 100), record(&sender, 30));
+        let fee = CreditAmount::from_microcredits(10);
+
+        // Ensure the amount goes to the recipient and the change of both records back to the sender
+        let plan =
+            program_manager.plan_transfer(60, fee, recipient, None, input_record.clone(), fee_record.clone()).unwrap();
+        assert_eq!(plan.input_record, input_record);
+        assert_eq!(plan.fee_record, fee_record);
+        assert_eq!(plan.recipient_record, PlannedRecord { owner: recipient, microcredits: 60 });
+        assert_eq!(plan.change_record, PlannedRecord { owner: sender, microcredits: 40 });
+        assert_eq!(plan.fee_change_record, PlannedRecord { owner: sender, microcredits: 20 });
+        assert_eq!(plan.fee, 10);
+
+        // Ensure spending whole records leaves empty change records
+        let plan = program_manager
+            .plan_transfer(
+                100,
+                CreditAmount::from_microcredits(30),
+                recipient,
+                None,
+                input_record.clone(),
+                fee_record.clone(),
+            )
+            .unwrap();
+        assert_eq!(plan.change_record.microcredits, 0);
+        assert_eq!(plan.fee_change_record.microcredits, 0);
+
+        // Ensure transfers can be sent to contacts by their label, but not to unknown labels
+        program_manager.add_contact("bob", recipient).unwrap();
+        let plan =
+            program_manager.plan_transfer(60, fee, "bob", None, input_record.clone(), fee_record.clone()).unwrap();
+        assert_eq!(plan.recipient_record.owner, recipient);
+        let error = program_manager
+            .plan_transfer(60, fee, "carol", None, input_record.clone(), fee_record.clone())
+            .unwrap_err();
+        assert!(error.to_string().contains("'carol'"));
+
+        // Ensure transfers which can't be made are rejected
+        let plan = |amount, fee, input_record: &Record<_, _>, fee_record: &Record<_, _>| {
+            program_manager.plan_transfer(
+                amount,
+                CreditAmount::from_microcredits(fee),
+                recipient,
+                None,
+                input_record.clone(),
+                fee_record.clone(),
+            )
+        };
+        assert!(plan(0, 10, &input_record, &fee_record).is_err());
+        assert!(plan(60, 0, &input_record, &fee_record).is_err());
+        assert!(plan(101, 10, &input_record, &fee_record).is_err());
+        assert!(plan(60, 31, &input_record, &fee_record).is_err());
+        assert!(plan(60, 10, &input_record, &input_record).is_err());
+        let insufficient_funds = |result: Result<_>| result.unwrap_err().downcast::<InsufficientFunds>().unwrap();
+        assert_eq!(insufficient_funds(plan(101, 30, &input_record, &fee_record)), InsufficientFunds {
+            required: 131,
+            available: 130
+        });
+        assert_eq!(insufficient_funds(plan(101, 10, &input_record, &fee_record)), InsufficientFunds {
+            required: 101,
+            available: 100
+        });
+        assert_eq!(insufficient_funds(plan(31, 31, &fee_record, &input_record)), InsufficientFunds {
+            required: 31,
+            available: 30
+        });
+        assert!(plan(60, 10, &record(&recipient, 100), &fee_record).is_err());
+        assert!(plan(60, 10, &input_record, &record(&recipient, 30)).is_err());
+    }
+
+    #[test]
+    fn test_finalize_transfer_plan() {
+        let private_key = PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap();
+        let sender = Address::try_from(&private_key).unwrap();
+        let recipient = Address::try_from(PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap()).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let (input_record, fee_record) = (record(&sender, 100), record(&sender, 30));
+        let plan = program_manager
+            .plan_transfer(60, CreditAmount::from_microcredits(10), recipient, None, input_record.clone(), fee_record)
+            .unwrap();
+        let finalize = |fee, records: &[Record<_, _>]| {
+            program_manager.finalize_transfer_plan(plan.clone(), CreditAmount::from_microcredits(fee), records)
+        };
+
+        // Ensure the planned records are kept while they cover the actual fee
+        let finalized = finalize(25, &[record(&sender, 26)]).unwrap();
+        assert_eq!((&finalized.input_record, &finalized.fee_record), (&plan.input_record, &plan.fee_record));
+        assert_eq!(finalized.fee_change_record, PlannedRecord { owner: sender, microcredits: 5 });
+        assert_eq!(finalized.change_record, plan.change_record);
+        assert_eq!(finalized.recipient_record, plan.recipient_record);
+        assert_eq!(finalized.fee, 25);
+
+        // Ensure the smallest record covering a higher fee is selected, keeping the input record
+        let (small, large) = (record(&sender, 35), record(&sender, 50));
+        let finalized = finalize(32, &[large.clone(), small.clone(), record(&recipient, 32)]).unwrap();
+        assert_eq!((&finalized.input_record, &finalized.fee_record), (&input_record, &small));
+        assert_eq!(finalized.fee_change_record.microcredits, 3);
+        assert_eq!(finalized.change_record.microcredits, 40);
+
+        // Ensure the input record pays the fee if no other record covers it
+        let other = record(&sender, 70);
+        let finalized = finalize(95, &[other.clone()]).unwrap();
+        assert_eq!((&finalized.input_record, &finalized.fee_record), (&other, &input_record));
+        assert_eq!(finalized.change_record, PlannedRecord { owner: sender, microcredits: 10 });
+        assert_eq!(finalized.fee_change_record.microcredits, 5);
+
+        // Ensure reserved records aren't selected and the selection fails if nothing covers the fee
+        let _reservation = program_manager.reserve_records(&[&large]).unwrap();
+        let insufficient_funds = |result: Result<_>| result.unwrap_err().downcast::<InsufficientFunds>().unwrap();
+        let error = insufficient_funds(finalize(101, &[large, record(&recipient, 200)]));
+        assert_eq!(error, InsufficientFunds { required: 101, available: 30 });
+        assert!(finalize(0, &[]).is_err());
+    }
+
     #[test]
     #[ignore]
     fn test_transfer() {
@@ -102,7 +438,14 @@ mod tests {
             }
 
             let (input_record, fee_record) = records.unwrap();
-            let result = program_manager.transfer(100, 500000, recipient_address, None, input_record, fee_record);
+            let result = program_manager.transfer(
+                100,
+                CreditAmount::from_microcredits(500000),
+                recipient_address,
+                None,
+                input_record,
+                fee_record,
+            );
             if result.is_err() {
                 println!("Transfer error: {} - retrying", result.unwrap_err());
             } else if i > 8 {
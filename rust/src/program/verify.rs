@@ -0,0 +1,412 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use rand::{CryptoRng, Rng};
+use snarkvm::synthesizer::{Fee, Transactions};
+use snarkvm_console::prelude::has_duplicates;
+
+/// The stages of replaying a transaction with [ProgramManager::debug_replay], in the order
+/// they are run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayStage {
+    /// Checking the transaction ID, the fee and the execution or deployment proofs
+    Verification,
+    /// Checking the transaction against the state of the network, i.e. that its input records
+    /// are unspent and the program it deploys doesn't exist yet
+    State,
+    /// Finalizing the transaction into a local copy of the VM state
+    Finalize,
+}
+
+/// The outcome of replaying a transaction with [ProgramManager::debug_replay]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayReport<N: Network> {
+    transaction_id: N::TransactionID,
+    passed: Vec<ReplayStage>,
+    failure: Option<(ReplayStage, String)>,
+}
+
+impl<N: Network> ReplayReport<N> {
+    /// Get the ID of the replayed transaction
+    pub fn transaction_id(&self) -> N::TransactionID {
+        self.transaction_id
+    }
+
+    /// Get the stages the transaction passed
+    pub fn passed_stages(&self) -> &[ReplayStage] {
+        &self.passed
+    }
+
+    /// Get the stage the transaction failed and the reason it failed, if it failed one
+    pub fn failure(&self) -> Option<(ReplayStage, &str)> {
+        self.failure.as_ref().map(|(stage, reason)| (*stage, reason.as_str()))
+    }
+
+    /// Determine if the transaction passed every stage of the replay
+    pub fn is_valid(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Verify a transaction locally before broadcasting it to the network.
+    ///
+    /// The programs executed by the transaction (or imported by the program it deploys) are
+    /// found in the program manager, on disk or on the Aleo network and loaded into a local VM
+    /// which checks the transaction ID, the fee and the execution or deployment proofs. Returns
+    /// `true` if the transaction is valid, otherwise an error naming the transitions which failed
+    /// verification. Transactions paying less than [ProgramManager::minimum_fee_for] are rejected
+    /// before their proofs are checked.
+    ///
+    /// The local VM holds no blocks, so the global state root a transaction was built against
+    /// isn't checked to be a state root of the network. The network checks it when the
+    /// transaction is broadcast.
+    pub fn verify_transaction(&self, transaction: &Transaction<N>) -> Result<bool> {
+        let vm = self.transaction_vm(transaction)?;
+        self.report_progress(TransactionPhase::Verifying);
+        self.check_transaction(&vm, transaction)?;
+        Ok(true)
+    }
+
+    /// Compute the minimum fee in microcredits a transaction has to pay, i.e. to check the fee of
+    /// a transaction built offline before broadcasting it.
+    ///
+    /// Executions aren't priced by the network, so they may pay any fee. Deployments have to pay
+    /// one microcredit per byte of the deploy transaction in its binary encoding, which is also
+    /// checked before a deployment is broadcast.
+    pub fn minimum_fee_for(&self, transaction: &Transaction<N>) -> Result<u64> {
+        match transaction {
+            Transaction::Deploy(..) => Ok(u64::try_from(transaction.to_bytes_le()?.len())?),
+            Transaction::Execute(..) => Ok(0),
+        }
+    }
+
+    /// Replay a transaction against a local copy of the VM state to find out why the network
+    /// would reject it.
+    ///
+    /// The transaction is verified like [ProgramManager::verify_transaction], checked against
+    /// the state of the network and finalized in an ephemeral VM, stopping at the first stage it
+    /// fails. The report names that stage and the reason the transaction failed it. Nothing is
+    /// broadcast and the network is only queried, so replaying a transaction has no effect on
+    /// any state. An error is returned if the replay itself can't be run, i.e. if the programs
+    /// of the transaction can't be found or the network can't be queried.
+    pub fn debug_replay(&self, transaction: &Transaction<N>) -> Result<ReplayReport<N>> {
+        let api_client = self.api_client()?;
+        let vm = self.transaction_vm(transaction)?;
+        let mut report = ReplayReport { transaction_id: transaction.id(), passed: vec![], failure: None };
+
+        let stages = [ReplayStage::Verification, ReplayStage::State, ReplayStage::Finalize];
+        for stage in stages {
+            let failure = match stage {
+                ReplayStage::Verification => {
+                    self.check_transaction(&vm, transaction).err().map(|error| error.to_string())
+                }
+                ReplayStage::State => Self::find_state_conflict(api_client, transaction)?,
+                ReplayStage::Finalize => vm
+                    .finalize(&Transactions::from(&[transaction.clone()]))
+                    .err()
+                    .map(|error| format!("❌ Transaction {} failed to finalize: {error}", transaction.id())),
+            };
+            if let Some(reason) = failure {
+                report.failure = Some((stage, reason));
+                break;
+            }
+            report.passed.push(stage);
+        }
+        Ok(report)
+    }
+
+    // Create an ephemeral VM containing the programs needed to verify the transaction
+    fn transaction_vm(&self, transaction: &Transaction<N>) -> Result<VM<N, ConsensusMemory<N>>> {
+        let rng = &mut rand::thread_rng();
+        let store = ConsensusStore::<N, ConsensusMemory<N>>::open(None)?;
+        let vm = VM::<N, ConsensusMemory<N>>::from(store)?;
+        let program_ids = match transaction {
+            Transaction::Deploy(_, _, deployment, _) => {
+                deployment.program().imports().keys().copied().collect::<Vec<_>>()
+            }
+            Transaction::Execute(_, execution, _) => {
+                execution.transitions().map(|transition| *transition.program_id()).collect()
+            }
+        };
+        program_ids.iter().try_for_each(|program_id| self.add_program_to_vm(&vm, program_id, rng))?;
+        Ok(vm)
+    }
+
+    // Verify the transaction in a VM and identify the transitions responsible for any failure.
+    //
+    // `VM::check_transaction` also requires the state root the transaction was built against to
+    // be in the VM's block store. The VM is built from an empty store, so that check would reject
+    // every transaction built against the network's state. The checks it makes on the transaction
+    // itself are run here instead, leaving state root membership to the network.
+    fn check_transaction(&self, vm: &VM<N, ConsensusMemory<N>>, transaction: &Transaction<N>) -> Result<()> {
+        let transaction_id = transaction.id();
+        let (fee_paid, minimum_fee) = (*transaction.fee()?, self.minimum_fee_for(transaction)?);
+        ensure!(
+            fee_paid >= minimum_fee,
+            "❌ Transaction {transaction_id} pays a fee of {fee_paid} microcredits, below the minimum fee of {minimum_fee} microcredits"
+        );
+        ensure!(*transaction_id == transaction.to_root()?, "❌ Transaction {transaction_id} has an incorrect ID");
+        ensure!(
+            !has_duplicates(transaction.transition_ids())
+                && !has_duplicates(transaction.transition_public_keys())
+                && !has_duplicates(transaction.serial_numbers())
+                && !has_duplicates(transaction.commitments())
+                && !has_duplicates(transaction.nonces()),
+            "❌ Transaction {transaction_id} contains duplicate transitions, serial numbers, commitments or nonces"
+        );
+
+        let check_fee = |fee: &Fee<N>| {
+            vm.process().read().verify_fee(fee).map_err(|error| {
+                anyhow!(
+                    "❌ Transaction {transaction_id} failed verification in fee transition {}: {error}",
+                    fee.transition_id()
+                )
+            })
+        };
+        match transaction {
+            Transaction::Deploy(_, owner, deployment, fee) => {
+                Transaction::check_deployment_size(deployment)?;
+                let program = deployment.program();
+                ensure!(
+                    program.mappings().is_empty()
+                        && program.functions().values().all(|function| function.finalize().is_none()),
+                    "❌ Transaction {transaction_id} deploys program {} with a mapping or finalize scope, which testnet3 does not accept",
+                    program.id()
+                );
+                ensure!(owner.verify(transaction_id), "❌ Transaction {transaction_id} has an invalid owner signature");
+                check_fee(fee)?;
+                ensure!(
+                    vm.verify_deployment(deployment),
+                    "❌ Transaction {transaction_id} failed verification in the deployment of program {}",
+                    program.id()
+                );
+            }
+            Transaction::Execute(_, execution, fee) => {
+                Transaction::check_execution_size(execution)?;
+                ensure!(
+                    fee.is_some() || transaction.is_coinbase() || transaction.is_split(),
+                    "❌ Transaction {transaction_id} is missing a fee"
+                );
+                fee.as_ref().map_or(Ok(()), check_fee)?;
+                if let Err(error) = vm.process().read().verify_execution::<true>(execution) {
+                    let transitions = execution
+                        .transitions()
+                        .map(|transition| {
+                            format!("{} ({}/{})", transition.id(), transition.program_id(), transition.function_name())
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    bail!(
+                        "❌ Transaction {transaction_id} failed verification in transition(s) {transitions}: {error}"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Find the reason the state of the network prevents a transaction from being accepted, if
+    // there is one
+    fn find_state_conflict(api_client: &AleoAPIClient<N, T>, transaction: &Transaction<N>) -> Result<Option<String>> {
+        let transaction_id = transaction.id();
+        if let Transaction::Deploy(_, _, deployment, _) = transaction {
+            let program_id = deployment.program().id();
+            if api_client.get_program(program_id).is_ok() {
+                return Ok(Some(format!(
+                    "❌ Transaction {transaction_id} deploys program {program_id} which already exists on the network"
+                )));
+            }
+        }
+        for transition in transaction.transitions() {
+            for serial_number in transition.serial_numbers() {
+                if api_client.is_serial_number_spent(*serial_number)? {
+                    return Ok(Some(format!(
+                        "❌ Transaction {transaction_id} spends an input record of transition {} ({}/{}) which is already spent (serial number {serial_number})",
+                        transition.id(),
+                        transition.program_id(),
+                        transition.function_name()
+                    )));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    // Load a program and its imports into a VM, synthesizing the keys for each of them
+    pub(crate) fn add_program_to_vm<R: Rng + CryptoRng>(
+        &self,
+        vm: &VM<N, ConsensusMemory<N>>,
+        program_id: &ProgramID<N>,
+        rng: &mut R,
+    ) -> Result<()> {
+        if vm.contains_program(program_id) {
+            return Ok(());
+        }
+        let program = self
+            .get_program(*program_id)
+            .or_else(|_| self.find_program(program_id))
+            .map_err(|_| anyhow!("❌ Program {program_id:?} could not be found locally or on the Aleo Network"))?;
+        program.imports().keys().try_for_each(|import_id| self.add_program_to_vm(vm, import_id, rng))?;
+        let deployment = vm.deploy(&program, rng)?;
+        vm.process().write().finalize_deployment(vm.program_store(), &deployment)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "wasm"))]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{MockTransport, EXECUTE_TRANSACTION, HELLO_PROGRAM, RECIPIENT_ADDRESS, RECIPIENT_PRIVATE_KEY},
+        HttpMethod,
+    };
+    use snarkvm_console::network::Testnet3;
+
+    // Create a coinbase transaction, which can be built offline since it does not require a fee
+    fn mint_transaction(program_manager: &ProgramManager<Testnet3>) -> Transaction<Testnet3> {
+        let rng = &mut rand::thread_rng();
+        let private_key = program_manager.get_private_key(None).unwrap();
+        let store = ConsensusStore::<Testnet3, ConsensusMemory<Testnet3>>::open(None).unwrap();
+        let vm = VM::from(store).unwrap();
+        let inputs = [RECIPIENT_ADDRESS, "100u64"].into_iter();
+        Transaction::execute(&vm, &private_key, ("credits.aleo", "mint"), inputs, None, None, rng).unwrap()
+    }
+
+    #[test]
+    #[ignore]
+    fn test_verify_transaction() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let phases = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let reported_phases = phases.clone();
+        program_manager
+            .set_progress_callback(Some(std::sync::Arc::new(move |phase| reported_phases.lock().unwrap().push(phase))));
+
+        // Ensure a valid transaction is verified and the verification is reported
+        let transaction = mint_transaction(&program_manager);
+        assert!(program_manager.verify_transaction(&transaction).unwrap());
+        assert_eq!(*phases.lock().unwrap(), vec![TransactionPhase::Verifying]);
+
+        // Ensure a transaction carrying the proof of another transaction is rejected and the
+        // failing transition is identified
+        let other_transaction = mint_transaction(&program_manager);
+        let proof = |transaction: &Transaction<Testnet3>| transaction.transitions().next().unwrap().proof().to_string();
+        let tampered_transaction = Transaction::<Testnet3>::from_str(
+            &transaction.to_string().replace(&proof(&transaction), &proof(&other_transaction)),
+        )
+        .unwrap();
+        let transition_id = transaction.transitions().next().unwrap().id().to_string();
+        let error = program_manager.verify_transaction(&tampered_transaction).unwrap_err().to_string();
+        assert!(error.contains(&transition_id));
+        assert!(error.contains("credits.aleo/mint"));
+    }
+
+    #[test]
+    #[ignore]
+    fn test_verify_transaction_built_against_the_network() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let mut program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        program_manager.add_program(&Program::<Testnet3>::from_str(HELLO_PROGRAM).unwrap()).unwrap();
+
+        // Ensure a transaction built against a state root of the network, which the local VM
+        // doesn't hold, is verified
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+        let vm = program_manager.transaction_vm(&transaction).unwrap();
+        let Transaction::Execute(_, execution, _) = &transaction else { unreachable!() };
+        assert!(!vm.block_store().contains_state_root(&execution.global_state_root()).unwrap());
+        assert!(program_manager.verify_transaction(&transaction).unwrap());
+    }
+
+    #[test]
+    fn test_minimum_fee_for() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+
+        // Ensure executions may pay any fee
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+        assert_eq!(program_manager.minimum_fee_for(&transaction).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_find_state_conflict() {
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+        let serial_numbers = transaction.serial_numbers().collect::<Vec<_>>();
+        assert!(!serial_numbers.is_empty());
+        let url = |serial_number| format!("http://localhost:3030/testnet3/find/transitionID/{serial_number}");
+        let api_client = |transport| {
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", transport).unwrap()
+        };
+
+        // Ensure a transaction spending unspent records doesn't conflict with the network state
+        let unspent = serial_numbers.iter().fold(MockTransport::new(), |transport, serial_number| {
+            transport.with_response(HttpMethod::Get, &url(serial_number), 404, "Failed to find the transition ID")
+        });
+        let conflict =
+            ProgramManager::<Testnet3, MockTransport>::find_state_conflict(&api_client(unspent), &transaction);
+        assert_eq!(conflict.unwrap(), None);
+
+        // Ensure a transaction spending a spent record conflicts and the record is identified
+        let spent = serial_numbers.iter().fold(MockTransport::new(), |transport, serial_number| {
+            let transition_id = "\"as1k9grqdjtrzk5nanmvalyrhhwjzktu5x4g2s094397dds4p7umqxsh68nsz\"";
+            transport.with_response(HttpMethod::Get, &url(serial_number), 200, transition_id)
+        });
+        let conflict = ProgramManager::<Testnet3, MockTransport>::find_state_conflict(&api_client(spent), &transaction)
+            .unwrap()
+            .unwrap();
+        assert!(conflict.contains("already spent"));
+        assert!(conflict.contains(&serial_numbers[0].to_string()));
+
+        // Ensure failures to query the network are returned as errors rather than conflicts
+        let failing = MockTransport::new();
+        assert!(
+            ProgramManager::<Testnet3, MockTransport>::find_state_conflict(&api_client(failing), &transaction).is_err()
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_debug_replay() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let offline_program_manager = ProgramManager::<Testnet3>::new(Some(private_key), None, None, None).unwrap();
+        let transaction = mint_transaction(&offline_program_manager);
+        let api_client =
+            AleoAPIClient::<Testnet3, _>::with_transport("http://localhost:3030", "testnet3", MockTransport::new())
+                .unwrap();
+        let program_manager = ProgramManager::new(Some(private_key), None, Some(api_client), None).unwrap();
+
+        // Ensure a valid transaction passes every stage
+        let report = program_manager.debug_replay(&transaction).unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.transaction_id(), transaction.id());
+        assert_eq!(report.passed_stages(), [ReplayStage::Verification, ReplayStage::State, ReplayStage::Finalize]);
+
+        // Ensure a transaction with a tampered ID fails verification
+        let tampered_transaction = Transaction::<Testnet3>::from_str(
+            &transaction
+                .to_string()
+                .replace(&transaction.id().to_string(), &mint_transaction(&offline_program_manager).id().to_string()),
+        )
+        .unwrap();
+        let report = program_manager.debug_replay(&tampered_transaction).unwrap();
+        assert!(!report.is_valid());
+        assert!(report.passed_stages().is_empty());
+        assert_eq!(report.failure().unwrap().0, ReplayStage::Verification);
+    }
+}
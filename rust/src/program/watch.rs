@@ -0,0 +1,171 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo library.
+
+// The Aleo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use snarkvm::synthesizer::{Input, Output};
+use std::sync::mpsc::Sender;
+
+/// An event involving an address, sent by [ProgramManager::watch_address]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AddressEvent<N: Network> {
+    /// A transition has the address as one of its constant or public inputs or outputs
+    PublicTransition { block_height: u32, transaction_id: N::TransactionID, transition_id: N::TransitionID },
+    /// A record owned by the address was created
+    Record { block_height: u32, commitment: Field<N>, record: Record<N, Plaintext<N>> },
+}
+
+impl<N: Network, T: HttpTransport> ProgramManager<N, T> {
+    /// Watch the blocks added to the chain and send an event to `sender` for each of them
+    /// involving the given address, i.e. to update a wallet as soon as it receives credits.
+    ///
+    /// Private values are only readable by their owner, so records are only reported if the
+    /// address is the program manager's own address. For any other address only the transitions
+    /// where it appears as a constant or public value are reported. Blocks are followed with
+    /// [AleoAPIClient::subscribe_blocks], so the events of a block replaced by a reorganization
+    /// are sent again for the block replacing it.
+    ///
+    /// This blocks the calling thread. It returns once an event can't be sent because the
+    /// receiver was dropped, or with an error if the node can't be queried.
+    pub fn watch_address(
+        &self,
+        address: Address<N>,
+        sender: Sender<AddressEvent<N>>,
+        password: Option<&str>,
+    ) -> Result<()> {
+        let view_key = match self.get_private_key(password) {
+            Ok(private_key) => {
+                Some(ViewKey::try_from(private_key)?).filter(|view_key| view_key.to_address() == address)
+            }
+            Err(error) if error.is::<NoSigningKey>() => None,
+            Err(error) => return Err(error),
+        };
+
+        for block in self.api_client()?.subscribe_blocks() {
+            for event in Self::address_events(&block?, &address, view_key.as_ref())? {
+                if sender.send(event).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Find the events of a block involving an address, decrypting its records with its view key
+    // if it is given
+    fn address_events(
+        block: &Block<N>,
+        address: &Address<N>,
+        view_key: Option<&ViewKey<N>>,
+    ) -> Result<Vec<AddressEvent<N>>> {
+        let block_height = block.height();
+        let mut events = vec![];
+        for transaction in block.transactions().iter() {
+            for transition in transaction.transitions() {
+                let inputs = transition.inputs().iter().filter_map(|input| match input {
+                    Input::Constant(_, plaintext) | Input::Public(_, plaintext) => plaintext.as_ref(),
+                    _ => None,
+                });
+                let outputs = transition.outputs().iter().filter_map(|output| match output {
+                    Output::Constant(_, plaintext) | Output::Public(_, plaintext) => plaintext.as_ref(),
+                    _ => None,
+                });
+                if inputs.chain(outputs).any(|plaintext| Self::contains_address(plaintext, address)) {
+                    events.push(AddressEvent::PublicTransition {
+                        block_height,
+                        transaction_id: transaction.id(),
+                        transition_id: *transition.id(),
+                    });
+                }
+
+                if let Some(view_key) = view_key {
+                    for (commitment, record) in transition.records().filter(|(_, record)| record.is_owner(view_key)) {
+                        let record = record.decrypt(view_key)?;
+                        events.push(AddressEvent::Record { block_height, commitment: *commitment, record });
+                    }
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    // Determine if a plaintext value is the address or a struct holding it
+    fn contains_address(plaintext: &Plaintext<N>, address: &Address<N>) -> bool {
+        match plaintext {
+            Plaintext::Literal(Literal::Address(value), _) => value == address,
+            Plaintext::Literal(..) => false,
+            Plaintext::Struct(members, _) => members.values().any(|member| Self::contains_address(member, address)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{EXECUTE_TRANSACTION, RECIPIENT_ADDRESS, RECIPIENT_PRIVATE_KEY};
+
+    use snarkvm::{parameters::testnet3::GenesisBytes, synthesizer::Transactions};
+    use snarkvm_console::prelude::FromBytes;
+
+    #[test]
+    fn test_address_events() {
+        let private_key = PrivateKey::<Testnet3>::from_str(RECIPIENT_PRIVATE_KEY).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let address = Address::<Testnet3>::from_str(RECIPIENT_ADDRESS).unwrap();
+        let genesis = Block::<Testnet3>::from_bytes_le(GenesisBytes::load_bytes()).unwrap();
+        let transaction = Transaction::<Testnet3>::from_str(EXECUTE_TRANSACTION).unwrap();
+        let block = Block::new(
+            &private_key,
+            genesis.hash(),
+            *genesis.header(),
+            Transactions::from(&[transaction.clone()]),
+            None,
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+
+        // Ensure the records owned by the address are reported when its view key is known
+        let events = ProgramManager::<Testnet3>::address_events(&block, &address, Some(&view_key)).unwrap();
+        let (commitment, record) = transaction.records().next().unwrap();
+        assert_eq!(events, vec![AddressEvent::Record {
+            block_height: block.height(),
+            commitment: *commitment,
+            record: record.decrypt(&view_key).unwrap(),
+        }]);
+
+        // Ensure nothing is reported for the address without its view key, since it only appears
+        // in private values
+        assert!(ProgramManager::<Testnet3>::address_events(&block, &address, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_contains_address() {
+        let address = Address::<Testnet3>::from_str(RECIPIENT_ADDRESS).unwrap();
+        let other_address = Address::try_from(PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap()).unwrap();
+        let plaintext = |value: &str| Plaintext::<Testnet3>::from_str(value).unwrap();
+
+        // Ensure addresses are found as literals and in nested structs
+        assert!(ProgramManager::<Testnet3>::contains_address(&plaintext(RECIPIENT_ADDRESS), &address));
+        let nested = plaintext(&format!("{{ amount: 5u64, parties: {{ from: {other_address}, to: {address} }} }}"));
+        assert!(ProgramManager::<Testnet3>::contains_address(&nested, &address));
+        assert!(ProgramManager::<Testnet3>::contains_address(&nested, &other_address));
+
+        // Ensure other values don't match
+        assert!(!ProgramManager::<Testnet3>::contains_address(&plaintext("5u64"), &address));
+        let unrelated = plaintext(&format!("{{ owner: {other_address}, amount: 5u64 }}"));
+        assert!(!ProgramManager::<Testnet3>::contains_address(&unrelated, &address));
+    }
+}